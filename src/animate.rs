@@ -0,0 +1,76 @@
+use anyhow::Result;
+use std::io::{self, IsTerminal, Write};
+use std::ops::RangeInclusive;
+use std::thread;
+use std::time::Duration;
+
+/// Successive renderings of a simulation, starting from its initial state.
+pub type Frames<'a> = Box<dyn Iterator<Item = String> + 'a>;
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const RESET: &str = "\x1b[0m";
+
+/// Wraps `text` in the ANSI SGR `style` (e.g. `"1;33"`), or leaves it as is
+/// when `color` is off.
+pub fn paint(text: &str, style: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{}m{}{}", style, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// A grayscale style for `value` out of `max`, from dim to bright white.
+pub fn heat(value: u32, max: u32) -> String {
+    // 232..=255 are the 24 grays of the 256-color palette
+    let shade = 236 + value.min(max) * 19 / max.max(1);
+    format!("38;5;{}", shade)
+}
+
+/// Plays frames to stdout: on a terminal it redraws in place in color and
+/// waits `delay` between frames, otherwise it prints plain frames one after
+/// another without waiting.
+pub struct Animator {
+    pub delay: Duration,
+    /// Which frames to show, counting the initial state as step 0.
+    pub steps: RangeInclusive<usize>,
+    tty: bool,
+}
+
+impl Animator {
+    pub fn new(delay: Duration, steps: RangeInclusive<usize>) -> Self {
+        Animator {
+            delay,
+            steps,
+            tty: io::stdout().is_terminal(),
+        }
+    }
+
+    /// Whether frames should carry ANSI colors.
+    pub fn color(&self) -> bool {
+        self.tty
+    }
+
+    pub fn play(&self, title: &str, frames: Frames) -> Result<()> {
+        let mut out = io::stdout().lock();
+        let (first, last) = (*self.steps.start(), *self.steps.end());
+        let shown = frames
+            .enumerate()
+            .skip(first)
+            .take_while(|(step, _)| *step <= last);
+        for (idx, (step, frame)) in shown.enumerate() {
+            if self.tty {
+                if idx > 0 {
+                    thread::sleep(self.delay);
+                }
+                write!(out, "{}", CLEAR)?;
+            } else if idx > 0 {
+                writeln!(out)?;
+            }
+            writeln!(out, "{} - step {}", title, step)?;
+            writeln!(out, "{}", frame)?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
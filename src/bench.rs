@@ -0,0 +1,80 @@
+use crate::Solution;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// The separately timed stages of a solution.
+pub const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Mean and minimum wall-clock time of one stage, in nanoseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub mean_ns: u64,
+    pub min_ns: u64,
+}
+
+impl Stats {
+    fn from_samples(samples: &[Duration]) -> Self {
+        let total: Duration = samples.iter().sum();
+        let mean = total / samples.len().max(1) as u32;
+        let min = samples.iter().min().copied().unwrap_or_default();
+        Stats {
+            mean_ns: mean.as_nanos() as u64,
+            min_ns: min.as_nanos() as u64,
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        Duration::from_nanos(self.mean_ns)
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+}
+
+/// Timings of every stage of one day, in the order of `STAGES`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DayTimings {
+    pub day: u8,
+    pub stages: [Stats; 3],
+}
+
+fn sample<T>(runs: usize, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        black_box(f()?);
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(&samples))
+}
+
+/// Times parsing and both parts of `S` on `input`, `runs` times each.
+pub fn time<S: Solution>(input: &str, runs: usize) -> Result<[Stats; 3]> {
+    let parsed = S::parse(input)?;
+    Ok([
+        sample(runs, || S::parse(black_box(input)))?,
+        sample(runs, || S::part1(black_box(&parsed)))?,
+        sample(runs, || S::part2(black_box(&parsed)))?,
+    ])
+}
+
+pub fn save_baseline(path: &Path, timings: &[DayTimings]) -> Result<()> {
+    let json = serde_json::to_string_pretty(timings)?;
+    fs::write(path, json).with_context(|| format!("Couldn't write {}", path.display()))
+}
+
+pub fn load_baseline(path: &Path) -> Result<Vec<DayTimings>> {
+    let json =
+        fs::read_to_string(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("Malformed baseline {}", path.display()))
+}
+
+/// Relative change of the minimum time against a baseline, e.g. `0.25` for 25% slower.
+pub fn change(current: &Stats, baseline: &Stats) -> f64 {
+    current.min_ns as f64 / baseline.min_ns.max(1) as f64 - 1.0
+}
@@ -0,0 +1,65 @@
+use crate::flags::{Flag, Flags};
+use crate::sonar::{self, windowed_sweep, Profile};
+use crate::Solution;
+use anyhow::{ensure, Context, Result};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| {
+                line.parse::<u32>()
+                    .with_context(|| format!("Failed to parse depth '{}'", line))
+            })
+            .collect()
+    }
+
+    fn part1(depths: &Self::Input) -> Result<Self::Answer1> {
+        Ok(windowed_sweep(depths, 1).increases)
+    }
+
+    fn part2(depths: &Self::Input) -> Result<Self::Answer2> {
+        Ok(windowed_sweep(depths, 3).increases)
+    }
+
+    const FLAGS: &'static [Flag] = &[
+        Flag::with_value("--sweep", "RANGE"),
+        Flag::switch("--profile"),
+        Flag::with_value("--smooth", "N").needing(&["--profile"]),
+        Flag::switch("--csv").needing(&["--sweep", "--profile"]),
+    ];
+
+    const HELP: &'static str = "\
+--sweep counts how often the sum of a sliding window of depths increases,
+decreases or stays level on day 1, for each window size in RANGE.
+--profile finds the longest increasing run and the largest drop in depth,
+and lists the depths averaged over windows of N (default 3) with their
+local extrema. --csv prints either table as comma-separated values.";
+
+    /// The sweep over window sizes and the depth profile, as tables.
+    fn report(depths: &Self::Input, flags: &Flags) -> Result<()> {
+        let csv = flags.has("--csv");
+        if let Some(sizes) = flags.range("--sweep")? {
+            sonar::sweep_table(&sonar::sweep_sizes(depths, sizes)).print(csv);
+        }
+        if flags.has("--profile") {
+            let smoothing = flags.parse("--smooth")?.unwrap_or(3);
+            ensure!(smoothing > 0, "--smooth needs a window of at least 1");
+            ensure!(
+                smoothing <= depths.len(),
+                "--smooth can't be wider than the {} depths",
+                depths.len()
+            );
+            let profile = Profile::new(depths, smoothing);
+            profile.summary().print(csv);
+            profile.series().print(csv);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,605 @@
+use crate::flags::{Flag, Flags};
+use crate::table::Table;
+use crate::{InputError, Solution};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use std::fmt;
+use std::io;
+use std::iter::{Enumerate, Peekable};
+use std::ops::Range;
+use std::str::FromStr;
+use std::vec;
+
+/// What to do when a command would take the submarine above the surface.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Surface {
+    /// Fail, naming the command, as a submarine can't fly.
+    #[default]
+    Reject,
+    /// Stop at the surface, at depth 0.
+    Clamp,
+    /// Let the depth go negative.
+    Allow,
+}
+
+impl FromStr for Surface {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "reject" => Ok(Surface::Reject),
+            "clamp" => Ok(Surface::Clamp),
+            "allow" => Ok(Surface::Allow),
+            _ => bail!("Unknown surface '{}', expected reject, clamp or allow", s),
+        }
+    }
+}
+
+/// Which way along the x axis the submarine is pointing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Heading {
+    /// Towards growing x.
+    #[default]
+    Ahead,
+    Astern,
+}
+
+impl Heading {
+    pub fn reversed(self) -> Self {
+        match self {
+            Heading::Ahead => Heading::Astern,
+            Heading::Astern => Heading::Ahead,
+        }
+    }
+
+    /// The change in x for one unit moved forward.
+    pub fn sign(self) -> i64 {
+        match self {
+            Heading::Ahead => 1,
+            Heading::Astern => -1,
+        }
+    }
+}
+
+/// Where the submarine is; depth grows downwards from the surface at 0.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    pub x: i64,
+    pub depth: i64,
+    pub aim: i64,
+    pub heading: Heading,
+}
+
+impl Position {
+    pub fn new() -> Position {
+        Position::default()
+    }
+
+    /// The horizontal position times the depth.
+    pub fn product(&self) -> Result<i64> {
+        self.x
+            .checked_mul(self.depth)
+            .with_context(|| format!("{} x {} overflows", self.x, self.depth))
+    }
+
+    /// Carries out `action` under `model`.
+    pub fn execute(self, action: Move, model: &dyn Model, surface: Surface) -> Result<Position> {
+        let next = match action {
+            Move::Forward(units) => model.advance(self, units.into())?,
+            Move::Back(units) => model.advance(self, -i64::from(units))?,
+            Move::Down(units) => model.dive(self, units.into())?,
+            Move::Up(units) => model.dive(self, -i64::from(units))?,
+            Move::Turn => Position {
+                heading: self.heading.reversed(),
+                ..self
+            },
+        };
+        next.surfaced(surface)
+    }
+
+    fn surfaced(mut self, surface: Surface) -> Result<Position> {
+        if self.depth < 0 {
+            match surface {
+                Surface::Reject => bail!(
+                    "the submarine would be {} above the surface",
+                    -self.depth
+                ),
+                Surface::Clamp => self.depth = 0,
+                Surface::Allow => {}
+            }
+        }
+        Ok(self)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x {}, depth {}, aim {}", self.x, self.depth, self.aim)?;
+        if self.heading == Heading::Astern {
+            write!(f, ", turned around")?;
+        }
+        Ok(())
+    }
+}
+
+fn offset(value: i64, by: i64) -> Result<i64> {
+    value
+        .checked_add(by)
+        .with_context(|| format!("{} + {} overflows", value, by))
+}
+
+/// How the submarine responds to the two kinds of movement; turning around
+/// is the same under every model.
+pub trait Model {
+    /// What the model is called on the command line.
+    fn name(&self) -> &'static str;
+
+    /// Moves `units` the way the submarine points, or backwards if negative.
+    fn advance(&self, pos: Position, units: i64) -> Result<Position>;
+
+    /// Handles `down` for positive `units` and `up` for negative ones.
+    fn dive(&self, pos: Position, units: i64) -> Result<Position>;
+}
+
+/// Part 1: `up` and `down` change the depth directly.
+pub struct Plain;
+
+impl Model for Plain {
+    fn name(&self) -> &'static str {
+        "plain"
+    }
+
+    fn advance(&self, pos: Position, units: i64) -> Result<Position> {
+        let x = offset(pos.x, units * pos.heading.sign())?;
+        Ok(Position { x, ..pos })
+    }
+
+    fn dive(&self, pos: Position, units: i64) -> Result<Position> {
+        let depth = offset(pos.depth, units)?;
+        Ok(Position { depth, ..pos })
+    }
+}
+
+/// Part 2: `up` and `down` change the aim, which moving then dives along.
+pub struct Aimed;
+
+impl Model for Aimed {
+    fn name(&self) -> &'static str {
+        "aimed"
+    }
+
+    fn advance(&self, pos: Position, units: i64) -> Result<Position> {
+        let dive = units.checked_mul(pos.aim).context("the dive overflows")?;
+        let x = offset(pos.x, units * pos.heading.sign())?;
+        let depth = offset(pos.depth, dive)?;
+        Ok(Position { x, depth, ..pos })
+    }
+
+    fn dive(&self, pos: Position, units: i64) -> Result<Position> {
+        let aim = offset(pos.aim, units)?;
+        Ok(Position { aim, ..pos })
+    }
+}
+
+pub const MODELS: &[&dyn Model] = &[&Plain, &Aimed];
+
+pub fn find_model(name: &str) -> Option<&'static dyn Model> {
+    MODELS.iter().copied().find(|model| model.name() == name)
+}
+
+/// A single movement of the submarine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Forward(u32),
+    Back(u32),
+    Up(u32),
+    Down(u32),
+    /// Turns the submarine around, swapping forwards and backwards.
+    Turn,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Move::Forward(units) => write!(f, "forward {}", units),
+            Move::Back(units) => write!(f, "back {}", units),
+            Move::Up(units) => write!(f, "up {}", units),
+            Move::Down(units) => write!(f, "down {}", units),
+            Move::Turn => write!(f, "turn"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Move(Move),
+    /// Runs the block `count` times.
+    Repeat(u32, Vec<Statement>),
+}
+
+/// A command and the 1-based line it starts on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Statement {
+    pub line: usize,
+    pub command: Command,
+}
+
+/// A word or brace of a script, with its line and byte span in that line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub line: usize,
+    pub span: Range<usize>,
+    pub text: &'a str,
+}
+
+/// Splits a script into words and braces, dropping `#` comments.
+pub fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let code = line.split('#').next().unwrap_or_default();
+        let token = |span: Range<usize>| Token {
+            line: idx + 1,
+            text: &code[span.clone()],
+            span,
+        };
+        let mut start = None;
+        for (pos, c) in code.char_indices().chain([(code.len(), ' ')]) {
+            let brace = c == '{' || c == '}';
+            if c.is_whitespace() || brace {
+                if let Some(start) = start.take() {
+                    tokens.push(token(start..pos));
+                }
+                if brace {
+                    tokens.push(token(pos..pos + 1));
+                }
+            } else if start.is_none() {
+                start = Some(pos);
+            }
+        }
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: Peekable<vec::IntoIter<Token<'a>>>,
+}
+
+impl<'a> Parser<'a> {
+    /// Parses statements up to the end of the input, or up to the `}`
+    /// matching `open`.
+    fn block(&mut self, open: Option<&Token>) -> Result<Vec<Statement>, InputError> {
+        let mut body = Vec::new();
+        while let Some(token) = self.tokens.next() {
+            let command = match token.text {
+                "}" if open.is_some() => return Ok(body),
+                "forward" => Command::Move(Move::Forward(self.number(&token, "a distance")?)),
+                "back" => Command::Move(Move::Back(self.number(&token, "a distance")?)),
+                "up" => Command::Move(Move::Up(self.number(&token, "a distance")?)),
+                "down" => Command::Move(Move::Down(self.number(&token, "a distance")?)),
+                "turn" => Command::Move(Move::Turn),
+                "repeat" => {
+                    let count = self.number(&token, "a repeat count")?;
+                    let brace = self.argument(&token, "{")?;
+                    if brace.text != "{" {
+                        return Err(unexpected(&brace, "{"));
+                    }
+                    Command::Repeat(count, self.block(Some(&brace))?)
+                }
+                _ => {
+                    return Err(unexpected(
+                        &token,
+                        "forward, back, up, down, turn or repeat",
+                    ))
+                }
+            };
+            body.push(Statement {
+                line: token.line,
+                command,
+            });
+        }
+        match open {
+            Some(open) => Err(InputError::missing(open.span.end, "a closing }").at_line(open.line)),
+            None => Ok(body),
+        }
+    }
+
+    /// The next token if it's on the same line as `after`.
+    fn argument(&mut self, after: &Token, expected: &'static str) -> Result<Token<'a>, InputError> {
+        self.tokens
+            .next_if(|token| token.line == after.line)
+            .ok_or_else(|| InputError::missing(after.span.end, expected).at_line(after.line))
+    }
+
+    fn number(&mut self, after: &Token, expected: &'static str) -> Result<u32, InputError> {
+        let token = self.argument(after, expected)?;
+        token.text.parse().map_err(|err| {
+            InputError::invalid(token.span.clone(), token.text, expected, err).at_line(token.line)
+        })
+    }
+}
+
+fn unexpected(token: &Token, expected: &'static str) -> InputError {
+    InputError::unexpected(token.span.clone(), token.text, expected).at_line(token.line)
+}
+
+/// How many rows of an aligned `--trace` are held and lined up at a time.
+const TRACE_PAGE: usize = 1000;
+
+/// How many moves a script may make, counting every pass through a
+/// repeated block, unless `Script::with_max_moves` says otherwise.
+pub const DEFAULT_MAX_MOVES: usize = 10_000_000;
+
+/// A program of submarine commands: `forward`, `back`, `up` and `down`
+/// followed by a distance, `turn`, and `repeat N { ... }` blocks. Commands
+/// can share a line and `#` starts a comment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Script {
+    pub statements: Vec<Statement>,
+    max_moves: usize,
+}
+
+impl FromStr for Script {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s).into_iter().peekable(),
+        };
+        Ok(Script {
+            statements: parser.block(None)?,
+            max_moves: DEFAULT_MAX_MOVES,
+        })
+    }
+}
+
+/// A move to make and the line of the script it comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub line: usize,
+    pub action: Move,
+}
+
+/// The moves of a script in the order they're made, with repeats unrolled.
+pub struct Steps<'a> {
+    /// The blocks being run: each one's statements, the index of the next
+    /// one and how many more times to run the block after this time.
+    stack: Vec<(&'a [Statement], usize, u32)>,
+}
+
+impl Iterator for Steps<'_> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        loop {
+            let (body, next, left) = self.stack.last_mut()?;
+            let Some(statement) = body.get(*next) else {
+                if *left == 0 {
+                    self.stack.pop();
+                } else {
+                    *left -= 1;
+                    *next = 0;
+                }
+                continue;
+            };
+            *next += 1;
+            match &statement.command {
+                Command::Move(action) => {
+                    return Some(Step {
+                        line: statement.line,
+                        action: *action,
+                    })
+                }
+                // Blocks without moves are skipped, however often they repeat
+                Command::Repeat(count, block) if *count > 0 && has_moves(block) => {
+                    self.stack.push((block, 0, count - 1))
+                }
+                Command::Repeat(..) => {}
+            }
+        }
+    }
+}
+
+fn has_moves(block: &[Statement]) -> bool {
+    block.iter().any(|statement| match &statement.command {
+        Command::Move(_) => true,
+        Command::Repeat(count, block) => *count > 0 && has_moves(block),
+    })
+}
+
+impl Script {
+    /// Stops runs and traces with an error once they've made `max_moves`
+    /// moves and the script asks for another.
+    pub fn with_max_moves(mut self, max_moves: usize) -> Self {
+        self.max_moves = max_moves;
+        self
+    }
+
+    pub fn steps(&self) -> Steps<'_> {
+        Steps {
+            stack: vec![(&self.statements, 0, 0)],
+        }
+    }
+
+    /// The positions the script passes through under `model`.
+    pub fn trace<'a>(&'a self, model: &'a dyn Model, surface: Surface) -> Trace<'a> {
+        Trace {
+            steps: self.steps().enumerate(),
+            pos: Position::new(),
+            model,
+            surface,
+            max_moves: self.max_moves,
+            failed: false,
+        }
+    }
+
+    /// Runs the script under `model`, naming the line of the first move
+    /// that fails.
+    pub fn run(&self, model: &dyn Model, surface: Surface) -> Result<Position> {
+        self.trace(model, surface)
+            .try_fold(Position::new(), |_, waypoint| Ok(waypoint?.pos))
+    }
+
+    /// Where the submarine is after its first `moves` moves, counting each
+    /// pass through a repeated block.
+    pub fn position_after(
+        &self,
+        moves: usize,
+        model: &dyn Model,
+        surface: Surface,
+    ) -> Result<Position> {
+        let (made, pos) = self
+            .trace(model, surface)
+            .take(moves)
+            .try_fold((0, Position::new()), |_, waypoint| {
+                let waypoint = waypoint?;
+                Ok::<_, anyhow::Error>((waypoint.index, waypoint.pos))
+            })?;
+        ensure!(made == moves, "The route only has {} moves", made);
+        Ok(pos)
+    }
+}
+
+/// The submarine's position after one of the moves of a script.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Waypoint {
+    /// How many moves have been made, counting this one.
+    pub index: usize,
+    pub step: Step,
+    pub pos: Position,
+}
+
+/// The positions a script passes through, ending with the error of the
+/// first move that fails or the first one past the script's limit.
+pub struct Trace<'a> {
+    steps: Enumerate<Steps<'a>>,
+    pos: Position,
+    model: &'a dyn Model,
+    surface: Surface,
+    max_moves: usize,
+    failed: bool,
+}
+
+impl Iterator for Trace<'_> {
+    type Item = Result<Waypoint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let (idx, step) = self.steps.next()?;
+        if idx == self.max_moves {
+            self.failed = true;
+            return Some(Err(anyhow!(
+                "Line {}: the script makes more than {} moves",
+                step.line,
+                self.max_moves
+            )));
+        }
+        match self.pos.execute(step.action, self.model, self.surface) {
+            Ok(pos) => {
+                self.pos = pos;
+                Some(Ok(Waypoint {
+                    index: idx + 1,
+                    step,
+                    pos,
+                }))
+            }
+            Err(err) => {
+                self.failed = true;
+                Some(Err(anyhow!(
+                    "Line {}: {}: {}",
+                    step.line,
+                    step.action,
+                    err
+                )))
+            }
+        }
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Script;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(script: &Self::Input) -> Result<Self::Answer1> {
+        script.run(&Plain, Surface::default())?.product()
+    }
+
+    fn part2(script: &Self::Input) -> Result<Self::Answer2> {
+        script.run(&Aimed, Surface::default())?.product()
+    }
+
+    const FLAGS: &'static [Flag] = &[
+        Flag::with_value("--model", "MODEL"),
+        Flag::with_value("--surface", "RULE"),
+        Flag::switch("--trace"),
+        Flag::with_value("--after", "N"),
+        Flag::switch("--csv").needing(&["--trace"]),
+    ];
+
+    const HELP: &'static str = "\
+--model runs the day 2 commands as a script under MODEL, plain (part 1)
+or aimed (part 2). Scripts can also go back N, turn around, repeat N { }
+a block of commands and have # comments, up to 10000000 moves in all.
+--trace lists where every move leaves the submarine and --after shows where
+it is after the first N moves, both under MODEL if given and aimed otherwise.
+Moves are counted as the script makes them, once for every pass through a
+repeat, not by input line; --csv prints the trace as comma-separated values.
+--surface picks what happens when a move would rise above the surface:
+reject fails (the default), clamp stops at depth 0 and allow goes on.";
+
+    /// Where the script takes the submarine under the chosen model, aimed
+    /// unless `--model` says otherwise, and rule for the surface, rejecting
+    /// flight unless `--surface` says otherwise.
+    fn report(script: &Self::Input, flags: &Flags) -> Result<()> {
+        let model = match flags.value("--model") {
+            Some(name) => find_model(name).with_context(|| {
+                let names: Vec<&str> = MODELS.iter().map(|m| m.name()).collect();
+                format!("Unknown model '{}', expected {}", name, names.join(" or "))
+            })?,
+            None => &Aimed,
+        };
+        let surface = match flags.value("--surface") {
+            Some(name) => name.parse()?,
+            None => Surface::default(),
+        };
+        if flags.has("--trace") {
+            let mut failure = None;
+            let rows = script.trace(model, surface).map_while(|waypoint| match waypoint {
+                Ok(Waypoint { index, step, pos }) => Some(vec![
+                    index.to_string(),
+                    step.line.to_string(),
+                    step.action.to_string(),
+                    pos.x.to_string(),
+                    pos.depth.to_string(),
+                    pos.aim.to_string(),
+                ]),
+                Err(err) => {
+                    failure = Some(err);
+                    None
+                }
+            });
+            let headers = ["Step", "Line", "Command", "X", "Depth", "Aim"];
+            let csv = flags.has("--csv");
+            Table::stream(&mut io::stdout().lock(), headers, rows, csv, TRACE_PAGE)?;
+            if let Some(err) = failure {
+                return Err(err);
+            }
+        }
+        if let Some(moves) = flags.parse("--after")? {
+            let pos = script.position_after(moves, model, surface)?;
+            println!("  {} after {} moves: {}", model.name(), moves, pos);
+        }
+        if flags.has("--model") || flags.has("--surface") {
+            let pos = script.run(model, surface)?;
+            println!("  {}: {}, product {}", model.name(), pos, pos.product()?);
+        }
+        Ok(())
+    }
+}
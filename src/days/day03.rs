@@ -1,4 +1,4 @@
-use advent::{get_my_lines, iter_lines};
+use crate::Solution;
 use anyhow::{anyhow, bail, Context, Result};
 use counter::Counter;
 
@@ -53,24 +53,26 @@ fn filter_by_bit(vec: &[String], cond: fn(&[String], usize) -> Result<u8>) -> Re
     bail!("Expected one element left, found: {}!", vec.len())
 }
 
-fn solve_p1() -> Result<usize> {
-    let lines: Vec<String> = get_my_lines!().collect();
-    let gamma = binary_to_int(&collect_bits(&lines, mcb_at)?)?;
-    let epsilon = binary_to_int(&collect_bits(&lines, lcb_at)?)?;
-    println!("gamma: {}, epsilon: {}", gamma, epsilon);
-    Ok(gamma * epsilon)
-}
+pub struct Day03;
 
-fn solve_p2() -> Result<usize> {
-    let lines: Vec<String> = get_my_lines!().collect();
-    let oxygen = binary_to_int(&filter_by_bit(&lines, mcb_at)?)?;
-    let co2 = binary_to_int(&filter_by_bit(&lines, lcb_at)?)?;
-    println!("oxygen: {}, co2: {}", oxygen, co2);
-    Ok(oxygen * co2)
-}
+impl Solution for Day03 {
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
 
-fn main() -> Result<()> {
-    println!("gamma * epsilon: {}", solve_p1()?);
-    println!("oxygen * co2: {}", solve_p2()?);
-    Ok(())
+    fn part1(lines: &Self::Input) -> Result<Self::Answer1> {
+        let gamma = binary_to_int(&collect_bits(lines, mcb_at)?)?;
+        let epsilon = binary_to_int(&collect_bits(lines, lcb_at)?)?;
+        Ok(gamma * epsilon)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<Self::Answer2> {
+        let oxygen = binary_to_int(&filter_by_bit(lines, mcb_at)?)?;
+        let co2 = binary_to_int(&filter_by_bit(lines, lcb_at)?)?;
+        Ok(oxygen * co2)
+    }
 }
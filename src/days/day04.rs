@@ -1,9 +1,9 @@
-use advent::{get_my_lines, iter_lines};
+use crate::Solution;
 use anyhow::{anyhow, bail, ensure, Context, Result};
 use std::num::ParseIntError;
 
-#[derive(Debug, Default)]
-struct Bingo {
+#[derive(Clone, Debug, Default)]
+pub struct Bingo {
     board: [[u8; 5]; 5],
     seen: [[bool; 5]; 5],
 }
@@ -83,50 +83,52 @@ fn parse_numbers(it: &mut impl Iterator<Item = String>) -> Result<Vec<u8>> {
         .context("Failed to parse bingo numbers!")
 }
 
-fn parse_input() -> Result<(Vec<u8>, Vec<Bingo>)> {
-    let mut lines = get_my_lines!();
-    let mut boards: Vec<Bingo> = Vec::new();
-    let numbers = parse_numbers(&mut lines)?;
-    loop {
-        let board = Bingo::new(&mut lines)?;
-        match board {
-            Some(board) => boards.push(board),
-            None => break,
-        }
-    }
-    Ok((numbers, boards))
-}
+pub struct Day04;
 
-fn solve_p1() -> Result<usize> {
-    let (numbers, mut boards) = parse_input()?;
-    for num in numbers {
-        for board in boards.iter_mut() {
-            if let Some(value) = board.mark(num) {
-                return Ok(value * num as usize);
+impl Solution for Day04 {
+    type Input = (Vec<u8>, Vec<Bingo>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = input.lines().map(String::from);
+        let mut boards: Vec<Bingo> = Vec::new();
+        let numbers = parse_numbers(&mut lines)?;
+        loop {
+            let board = Bingo::new(&mut lines)?;
+            match board {
+                Some(board) => boards.push(board),
+                None => break,
             }
         }
+        Ok((numbers, boards))
     }
-    bail!("No winning board found!")
-}
 
-fn solve_p2() -> Result<usize> {
-    let (numbers, mut boards) = parse_input()?;
-    for num in numbers {
-        let count = boards.len();
-        for board in boards.iter_mut() {
-            if let Some(value) = board.mark(num) {
-                if count == 1 {
+    fn part1((numbers, boards): &Self::Input) -> Result<Self::Answer1> {
+        let mut boards = boards.clone();
+        for &num in numbers {
+            for board in boards.iter_mut() {
+                if let Some(value) = board.mark(num) {
                     return Ok(value * num as usize);
                 }
             }
         }
-        boards.retain(|board| board.check() == None);
+        bail!("No winning board found!")
     }
-    bail!("No unique last-winning board found!")
-}
 
-fn main() -> Result<()> {
-    println!("First winning board value: {}", solve_p1()?);
-    println!("Last winning board value: {}", solve_p2()?);
-    Ok(())
+    fn part2((numbers, boards): &Self::Input) -> Result<Self::Answer2> {
+        let mut boards = boards.clone();
+        for &num in numbers {
+            let count = boards.len();
+            for board in boards.iter_mut() {
+                if let Some(value) = board.mark(num) {
+                    if count == 1 {
+                        return Ok(value * num as usize);
+                    }
+                }
+            }
+            boards.retain(|board| board.check().is_none());
+        }
+        bail!("No unique last-winning board found!")
+    }
 }
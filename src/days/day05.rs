@@ -0,0 +1,398 @@
+use crate::grid::Grid;
+use crate::Solution;
+use anyhow::{anyhow, bail, Context, Error, Result};
+use counter::Counter;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// Largest bounding box, in cells, that `Backend::Auto` maps densely.
+const DENSE_LIMIT: i64 = 1 << 22;
+
+/// How `count_overlaps` finds the points covered by several vents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Counts every covered point in a hash map.
+    Sparse,
+    /// Counts every covered point in an array spanning the vents' bounding box.
+    Dense,
+    /// Intersects the vents pairwise, never listing the points they cover,
+    /// so the cost only depends on the number of vents. Only handles
+    /// horizontal, vertical and diagonal vents.
+    Intersections,
+    /// `Dense` if the bounding box is small, otherwise `Intersections`, or
+    /// `Sparse` if some vents are sloped.
+    Auto,
+}
+
+/// What to do with vents that are neither horizontal, vertical nor diagonal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Slopes {
+    /// Fail to parse them, as the puzzle promises there are none.
+    #[default]
+    Reject,
+    /// Leave them out.
+    Skip,
+    /// Keep them, stepping one cell at a time along the longer axis and
+    /// covering the cell nearest to the line at each step.
+    Rasterize,
+}
+
+/// A line of vents between two endpoints, both included.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vent {
+    start: (i32, i32),
+    end: (i32, i32),
+}
+
+fn parse_tuple(s: &str) -> Result<(i32, i32)> {
+    let (x, y) = s
+        .split(',')
+        .map(|i| i.parse::<i32>())
+        .next_tuple()
+        .with_context(|| format!("Failed to split '{}' on comma", s))?;
+    Ok((x?, y?))
+}
+
+impl FromStr for Vent {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let (first, second) = s
+            .split(" -> ")
+            .next_tuple()
+            .ok_or_else(|| anyhow!("Failed to split '{}' on ->", s))?;
+        Ok(Vent::new(parse_tuple(first)?, parse_tuple(second)?))
+    }
+}
+
+impl std::fmt::Display for Vent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{} -> {},{}",
+            self.start.0, self.start.1, self.end.0, self.end.1
+        )
+    }
+}
+
+/// The cells of a vent from start to end, one step at a time.
+#[derive(Clone, Debug)]
+pub struct Points {
+    start: (i64, i64),
+    delta: (i64, i64),
+    steps: i64,
+    step: i64,
+}
+
+impl Points {
+    /// The cell on the line `step / steps` of the way along its major axis:
+    /// exact for aligned vents, otherwise the nearest one to the true line
+    /// with ties rounded up, so a vent covers the same cells both ways round.
+    fn at(&self, step: i64) -> (i32, i32) {
+        let coordinate = |from: i64, delta: i64| {
+            let steps = self.steps.max(1) as i128;
+            let twice = 2 * (from as i128 * steps + step as i128 * delta as i128) + steps;
+            twice.div_euclid(2 * steps) as i32
+        };
+        (
+            coordinate(self.start.0, self.delta.0),
+            coordinate(self.start.1, self.delta.1),
+        )
+    }
+}
+
+impl Iterator for Points {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step > self.steps {
+            return None;
+        }
+        self.step += 1;
+        Some(self.at(self.step - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = (self.steps + 1 - self.step) as usize;
+        (left, Some(left))
+    }
+}
+
+impl ExactSizeIterator for Points {}
+
+impl IntoIterator for &Vent {
+    type Item = (i32, i32);
+    type IntoIter = Points;
+
+    fn into_iter(self) -> Points {
+        self.points()
+    }
+}
+
+impl Vent {
+    pub fn new(start: (i32, i32), end: (i32, i32)) -> Self {
+        Vent { start, end }
+    }
+
+    pub fn start(&self) -> (i32, i32) {
+        self.start
+    }
+
+    pub fn end(&self) -> (i32, i32) {
+        self.end
+    }
+
+    /// Whether the vent is horizontal or vertical.
+    pub fn is_straight(&self) -> bool {
+        self.start.0 == self.end.0 || self.start.1 == self.end.1
+    }
+
+    /// Whether the vent is horizontal, vertical or at 45 degrees, so that
+    /// it passes exactly through every cell it covers.
+    pub fn is_aligned(&self) -> bool {
+        let (dx, dy) = self.delta();
+        dx == 0 || dy == 0 || dx.abs() == dy.abs()
+    }
+
+    fn delta(&self) -> (i64, i64) {
+        (
+            self.end.0 as i64 - self.start.0 as i64,
+            self.end.1 as i64 - self.start.1 as i64,
+        )
+    }
+
+    /// The cells the vent covers, from start to end, one per step along
+    /// whichever axis it spans further.
+    pub fn points(&self) -> Points {
+        let (dx, dy) = self.delta();
+        Points {
+            start: (self.start.0 as i64, self.start.1 as i64),
+            delta: (dx, dy),
+            steps: dx.abs().max(dy.abs()),
+            step: 0,
+        }
+    }
+}
+
+/// Parses one vent per line, handling sloped ones according to `slopes`.
+pub fn parse_vents(input: &str, slopes: Slopes) -> Result<Vec<Vent>> {
+    let mut vents = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let vent: Vent = line.parse().with_context(|| format!("Line {}", idx + 1))?;
+        match slopes {
+            _ if vent.is_aligned() => vents.push(vent),
+            Slopes::Reject => bail!(
+                "Line {}: vent {} is neither horizontal, vertical nor diagonal",
+                idx + 1,
+                vent
+            ),
+            Slopes::Skip => {}
+            Slopes::Rasterize => vents.push(vent),
+        }
+    }
+    Ok(vents)
+}
+
+/// The `(a, b)` of vertical, horizontal and both diagonal lines.
+const LINES: [(i64, i64); 4] = [(1, 0), (0, 1), (-1, 1), (1, 1)];
+
+/// The coordinate that varies along lines of direction `(a, b)`.
+fn param((a, b): (i64, i64), (x, y): (i64, i64)) -> i64 {
+    if (a, b) == LINES[0] {
+        y
+    } else {
+        x
+    }
+}
+
+/// The line a vent lies on, as `a * x + b * y = c`, with the range of the
+/// coordinate that varies along it (y for vertical lines, x otherwise).
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    a: i64,
+    b: i64,
+    c: i64,
+    lo: i64,
+    hi: i64,
+}
+
+impl Segment {
+    /// Only meaningful for aligned vents.
+    fn new(vent: &Vent) -> Self {
+        let (x0, y0) = (vent.start.0 as i64, vent.start.1 as i64);
+        let (x1, y1) = (vent.end.0 as i64, vent.end.1 as i64);
+        let (dx, dy) = vent.delta();
+        let (a, b) = match (dx.signum() * dy.signum(), dx) {
+            (0, 0) => LINES[0],
+            (0, _) => LINES[1],
+            (1, _) => LINES[2],
+            _ => LINES[3],
+        };
+        let (lo, hi) = (param((a, b), (x0, y0)), param((a, b), (x1, y1)));
+        Segment {
+            a,
+            b,
+            c: a * x0 + b * y0,
+            lo: lo.min(hi),
+            hi: lo.max(hi),
+        }
+    }
+
+    fn line(&self) -> (i64, i64, i64) {
+        (self.a, self.b, self.c)
+    }
+
+    fn contains(&self, point: (i64, i64)) -> bool {
+        self.a * point.0 + self.b * point.1 == self.c
+            && (self.lo..=self.hi).contains(&param((self.a, self.b), point))
+    }
+
+    /// The lattice point where two segments on different lines cross, if any.
+    fn crossing(&self, other: &Segment) -> Option<(i64, i64)> {
+        let det = self.a * other.b - other.a * self.b;
+        let x = self.c * other.b - other.c * self.b;
+        let y = self.a * other.c - other.a * self.c;
+        if det == 0 || x % det != 0 || y % det != 0 {
+            return None;
+        }
+        let point = (x / det, y / det);
+        (self.contains(point) && other.contains(point)).then_some(point)
+    }
+}
+
+fn sparse_overlaps(vents: &[Vent]) -> usize {
+    let mut sparse_map: Counter<(i32, i32)> = Counter::new();
+    for pos in vents.iter().flatten() {
+        sparse_map[&pos] += 1;
+    }
+    sparse_map.values().filter(|&v| *v > 1).count()
+}
+
+/// The smallest and largest x and y any vent reaches.
+fn bounds(vents: &[Vent]) -> ((i32, i32), (i32, i32)) {
+    let ends = vents.iter().flat_map(|vent| [vent.start, vent.end]);
+    let (xs, ys): (Vec<i32>, Vec<i32>) = ends.unzip();
+    let span = |v: &[i32]| (v.iter().min().copied(), v.iter().max().copied());
+    match (span(&xs), span(&ys)) {
+        ((Some(x0), Some(x1)), (Some(y0), Some(y1))) => ((x0, x1), (y0, y1)),
+        _ => ((0, 0), (0, 0)),
+    }
+}
+
+fn dense_overlaps(vents: &[Vent]) -> usize {
+    let ((x0, x1), (y0, y1)) = bounds(vents);
+    let (rows, cols) = (y1 as i64 - y0 as i64 + 1, x1 as i64 - x0 as i64 + 1);
+    let mut map = Grid::filled(rows as usize, cols as usize, 0u8);
+    for (x, y) in vents.iter().flatten() {
+        let cell = &mut map[((y - y0) as usize, (x - x0) as usize)];
+        *cell = cell.saturating_add(1);
+    }
+    map.values().filter(|&&v| v > 1).count()
+}
+
+fn intersection_overlaps(vents: &[Vent]) -> Result<usize> {
+    if let Some(vent) = vents.iter().find(|vent| !vent.is_aligned()) {
+        bail!("Can't intersect sloped vent {}, try another back-end", vent);
+    }
+    let segments: Vec<Segment> = vents.iter().map(Segment::new).collect();
+    // Collinear vents overlap along a stretch of their line, others cross
+    // in at most one point
+    let mut stretches: HashMap<(i64, i64, i64), Vec<(i64, i64)>> = HashMap::new();
+    let mut crossings = HashSet::new();
+    for (i, first) in segments.iter().enumerate() {
+        for second in &segments[i + 1..] {
+            if first.line() == second.line() {
+                let (lo, hi) = (first.lo.max(second.lo), first.hi.min(second.hi));
+                if lo <= hi {
+                    stretches.entry(first.line()).or_default().push((lo, hi));
+                }
+            } else if let Some(point) = first.crossing(second) {
+                crossings.insert(point);
+            }
+        }
+    }
+    let mut overlaps = Vec::new();
+    for (&(a, b, c), ranges) in &stretches {
+        let merged = merge(ranges).into_iter();
+        overlaps.extend(merged.map(|(lo, hi)| Segment { a, b, c, lo, hi }));
+    }
+    // How many of the overlaps, on different lines, pass through `point`
+    let covering = |point: (i64, i64)| {
+        overlaps
+            .iter()
+            .filter(|overlap| overlap.contains(point))
+            .count()
+    };
+    let mut count: usize = overlaps.iter().map(|o| (o.hi - o.lo + 1) as usize).sum();
+    // Overlaps that cross each other counted their shared point once each
+    let shared: HashSet<(i64, i64)> = overlaps
+        .iter()
+        .enumerate()
+        .flat_map(|(i, first)| {
+            overlaps[i + 1..]
+                .iter()
+                .filter_map(move |second| first.crossing(second))
+        })
+        .collect();
+    for point in shared {
+        count -= covering(point) - 1;
+    }
+    // Crossings inside an overlap were counted with it already
+    Ok(count + crossings.into_iter().filter(|&point| covering(point) == 0).count())
+}
+
+/// Merges overlapping ranges into sorted, disjoint ones.
+fn merge(ranges: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    let mut ranges = ranges.to_vec();
+    ranges.sort_unstable();
+    let mut merged: Vec<(i64, i64)> = Vec::new();
+    for (lo, hi) in ranges {
+        match merged.last_mut() {
+            Some((_, end)) if lo <= *end => *end = (*end).max(hi),
+            _ => merged.push((lo, hi)),
+        }
+    }
+    merged
+}
+
+/// Counts the points at least two vents pass through.
+pub fn count_overlaps(vents: &[Vent], backend: Backend) -> Result<usize> {
+    match backend {
+        Backend::Sparse => Ok(sparse_overlaps(vents)),
+        Backend::Dense => Ok(dense_overlaps(vents)),
+        Backend::Intersections => intersection_overlaps(vents),
+        Backend::Auto => {
+            let ((x0, x1), (y0, y1)) = bounds(vents);
+            // The full i32 range on both axes is too big for an i64 area
+            let area = (x1 as i64 - x0 as i64 + 1).checked_mul(y1 as i64 - y0 as i64 + 1);
+            if area.is_some_and(|area| area <= DENSE_LIMIT) {
+                Ok(dense_overlaps(vents))
+            } else if vents.iter().all(Vent::is_aligned) {
+                intersection_overlaps(vents)
+            } else {
+                Ok(sparse_overlaps(vents))
+            }
+        }
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Vent>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_vents(input, Slopes::default())
+    }
+
+    fn part1(vents: &Self::Input) -> Result<Self::Answer1> {
+        let straight: Vec<Vent> = vents.iter().filter(|v| v.is_straight()).cloned().collect();
+        count_overlaps(&straight, Backend::Auto)
+    }
+
+    fn part2(vents: &Self::Input) -> Result<Self::Answer2> {
+        count_overlaps(vents, Backend::Auto)
+    }
+}
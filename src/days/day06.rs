@@ -0,0 +1,74 @@
+use crate::animate::{self, Frames};
+use crate::Solution;
+use anyhow::Result;
+
+pub const MAX_AGE: usize = 9;
+const RESET_AGE: usize = 7;
+const BAR_WIDTH: usize = 50;
+
+fn grow_fish(fish: &mut [usize; MAX_AGE]) {
+    fish[RESET_AGE] += fish[0];
+    fish.rotate_left(1);
+}
+
+fn solve(fish: &[usize; MAX_AGE], days: usize) -> usize {
+    let mut fish = *fish;
+    for _ in 0..days {
+        grow_fish(&mut fish);
+    }
+    fish.iter().sum()
+}
+
+/// A bar chart of how many fish there are of each age, scaled to the
+/// largest group.
+fn chart(fish: &[usize; MAX_AGE], color: bool) -> String {
+    let largest = fish.iter().copied().max().unwrap_or(0).max(1);
+    let mut out = String::new();
+    for (age, &count) in fish.iter().enumerate() {
+        let bar = format!("{:<1$}", "#".repeat(count * BAR_WIDTH / largest), BAR_WIDTH);
+        // Newborn fish, still too young to spawn, are drawn in yellow
+        let style = if age >= RESET_AGE { "33" } else { "32" };
+        out += &format!(
+            "{} | {} {}\n",
+            age,
+            animate::paint(&bar, style, color),
+            count
+        );
+    }
+    out + &format!("Total: {}", fish.iter().sum::<usize>())
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = [usize; MAX_AGE];
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut fish: [usize; MAX_AGE] = [0; MAX_AGE];
+        for age in input.trim().split(',') {
+            fish[age.parse::<usize>()?] += 1;
+        }
+        Ok(fish)
+    }
+
+    fn part1(fish: &Self::Input) -> Result<Self::Answer1> {
+        Ok(solve(fish, 80))
+    }
+
+    fn part2(fish: &Self::Input) -> Result<Self::Answer2> {
+        Ok(solve(fish, 256))
+    }
+
+    /// The school on each of the 256 days of part 2.
+    fn frames(fish: &Self::Input, color: bool) -> Option<Frames<'_>> {
+        let mut fish = *fish;
+        let first = chart(&fish, color);
+        let rest = (0..256).map(move |_| {
+            grow_fish(&mut fish);
+            chart(&fish, color)
+        });
+        Some(Box::new(std::iter::once(first).chain(rest)))
+    }
+}
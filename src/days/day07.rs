@@ -0,0 +1,46 @@
+use crate::Solution;
+use anyhow::{Context, Result};
+use std::convert::identity;
+
+fn arithmetic_sum(n: i64) -> i64 {
+    n * (n + 1) / 2
+}
+
+fn fuel_to_reach(crabs: &[i64], pos: i64, cost: fn(i64) -> i64) -> i64 {
+    crabs.iter().map(|c| cost((pos - c).abs())).sum()
+}
+
+fn solve(crabs: &[i64], cost: fn(i64) -> i64) -> Result<i64> {
+    let max = *crabs.iter().max().context("No crabs!")?;
+    (0..max)
+        .map(|pos| fuel_to_reach(crabs, pos, cost))
+        .min()
+        .context("No minimum?")
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .trim()
+            .split(',')
+            .map(|v| {
+                v.parse::<i64>()
+                    .with_context(|| format!("Failed to parse {}", v))
+            })
+            .collect()
+    }
+
+    fn part1(crabs: &Self::Input) -> Result<Self::Answer1> {
+        solve(crabs, identity)
+    }
+
+    fn part2(crabs: &Self::Input) -> Result<Self::Answer2> {
+        solve(crabs, arithmetic_sum)
+    }
+}
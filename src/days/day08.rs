@@ -1,4 +1,4 @@
-use advent::{get_my_lines, iter_lines};
+use crate::Solution;
 use anyhow::{Context, Error, Result};
 use bimap::BiMap;
 use itertools::Itertools;
@@ -6,7 +6,7 @@ use std::collections::HashSet;
 use std::str::FromStr;
 
 #[derive(Debug, Default)]
-struct Signal {
+pub struct Signal {
     patterns: Vec<String>,
     value: Vec<String>,
 }
@@ -101,27 +101,29 @@ impl Signal {
     }
 }
 
-fn parse_input() -> Result<Vec<Signal>> {
-    get_my_lines!()
-        .map(|line| line.parse::<Signal>())
-        .collect::<Result<Vec<Signal>>>()
-}
+pub struct Day08;
 
-fn solve_p1(signals: &[Signal]) -> Result<usize> {
-    let unique_lengths: HashSet<_> = vec![2, 3, 4, 7].into_iter().collect();
-    Ok(signals
-        .iter()
-        .flat_map(|s| s.value.iter().filter(|v| unique_lengths.contains(&v.len())))
-        .count())
-}
+impl Solution for Day08 {
+    type Input = Vec<Signal>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn solve_p2(signals: &[Signal]) -> Result<usize> {
-    signals.iter().map(|s| s.output()).sum()
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| line.parse::<Signal>())
+            .collect::<Result<Vec<Signal>>>()
+    }
+
+    fn part1(signals: &Self::Input) -> Result<Self::Answer1> {
+        let unique_lengths: HashSet<_> = vec![2, 3, 4, 7].into_iter().collect();
+        Ok(signals
+            .iter()
+            .flat_map(|s| s.value.iter().filter(|v| unique_lengths.contains(&v.len())))
+            .count())
+    }
 
-fn main() -> Result<()> {
-    let signals = parse_input()?;
-    println!("Number of unique digits: {}", solve_p1(&signals)?);
-    println!("Sum of output values: {}", solve_p2(&signals)?);
-    Ok(())
+    fn part2(signals: &Self::Input) -> Result<Self::Answer2> {
+        signals.iter().map(|s| s.output()).sum()
+    }
 }
@@ -0,0 +1,73 @@
+use crate::grid::{Adjacency, Grid, Pos};
+use crate::image::{self, Overlay, Picture};
+use crate::Solution;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+
+/// The basin around `pos`: every cell reachable without crossing a 9.
+fn flood_fill(pos: Pos, cave: &Grid<u32>) -> HashSet<Pos> {
+    let mut seen: HashSet<Pos> = HashSet::new();
+    _flood_fill(pos, cave, &mut seen);
+    seen
+}
+
+fn _flood_fill(pos: Pos, cave: &Grid<u32>, seen: &mut HashSet<Pos>) {
+    if cave[pos] == 9 || seen.contains(&pos) {
+        return;
+    }
+    seen.insert(pos);
+    cave.neighbors(pos, Adjacency::FOUR)
+        .for_each(|p| _flood_fill(p, cave, seen));
+}
+
+fn low_points(cave: &Grid<u32>) -> Vec<Pos> {
+    cave.iter()
+        .filter(|&(pos, item)| {
+            cave.neighbors(pos, Adjacency::FOUR)
+                .all(|nbr| *item < cave[nbr])
+        })
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Grid<u32>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Grid::parse_digits(input.lines())
+    }
+
+    fn part1(cave: &Self::Input) -> Result<Self::Answer1> {
+        Ok(low_points(cave).into_iter().map(|pos| cave[pos] + 1).sum())
+    }
+
+    fn part2(cave: &Self::Input) -> Result<Self::Answer2> {
+        let mut basins: Vec<usize> = low_points(cave)
+            .into_iter()
+            .map(|pos| flood_fill(pos, cave).len())
+            .collect();
+        basins.sort_unstable();
+        basins.reverse();
+        basins
+            .into_iter()
+            .take(3)
+            .reduce(|a, b| a * b)
+            .context("No elements?!")
+    }
+
+    /// Heights in gray, with each basin in its own color.
+    fn picture(cave: &Self::Input) -> Result<Option<Picture>> {
+        let picture = Picture::new(cave.map(|&height| image::gray(height, 9)));
+        let basins = low_points(cave).into_iter().enumerate();
+        Ok(Some(basins.fold(picture, |picture, (idx, pos)| {
+            picture.with_overlay(Overlay::Region {
+                cells: flood_fill(pos, cave).into_iter().collect(),
+                color: image::palette(idx),
+            })
+        })))
+    }
+}
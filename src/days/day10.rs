@@ -1,5 +1,5 @@
-use advent::{get_my_lines, iter_lines};
-use anyhow::{bail, Result};
+use crate::Solution;
+use anyhow::{bail, Context, Result};
 use pest::error::InputLocation::Pos;
 use pest::Parser;
 use pest_derive::Parser;
@@ -44,7 +44,7 @@ fn err_score(c: char) -> Result<usize> {
 fn autocomplete(line: &str) -> Result<usize> {
     let mut line = line.to_string();
     let mut score = 0;
-    let terminals = vec![')', ']', '}', '>'];
+    let terminals = [')', ']', '}', '>'];
     loop {
         for (idx, term) in terminals.iter().enumerate() {
             let attempt = format!("{}{}", &line, &term);
@@ -63,19 +63,38 @@ fn autocomplete(line: &str) -> Result<usize> {
     }
 }
 
-fn main() -> Result<()> {
-    let mut error_score: usize = 0;
-    let mut completions: Vec<usize> = Vec::new();
-    for line in get_my_lines!() {
-        match try_parse(&line) {
-            ParseResult::Corrupted(n) => error_score += err_score(line.as_bytes()[n] as char)?,
-            ParseResult::Incomplete => completions.push(autocomplete(&line)?),
-            ParseResult::Success => println!("No parsing error on this line"),
-        };
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(lines: &Self::Input) -> Result<Self::Answer1> {
+        let mut error_score: usize = 0;
+        for line in lines {
+            if let ParseResult::Corrupted(n) = try_parse(line) {
+                error_score += err_score(line.as_bytes()[n] as char)?;
+            }
+        }
+        Ok(error_score)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<Self::Answer2> {
+        let mut completions: Vec<usize> = Vec::new();
+        for line in lines {
+            if let ParseResult::Incomplete = try_parse(line) {
+                completions.push(autocomplete(line)?);
+            }
+        }
+        completions.sort_unstable();
+        completions
+            .get(completions.len().saturating_sub(1) / 2)
+            .copied()
+            .context("No incomplete lines!")
     }
-    completions.sort_unstable();
-    let comp_score = completions[(completions.len() - 1) / 2];
-    println!("Error score: {}", error_score);
-    println!("Completion score: {}", comp_score);
-    Ok(())
 }
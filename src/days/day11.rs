@@ -0,0 +1,255 @@
+use crate::animate::{self, Frames};
+use crate::grid::{Adjacency, Grid, Pos};
+use crate::image::{self, Picture};
+use crate::Solution;
+use anyhow::{ensure, Context, Result};
+use itertools::Itertools;
+use std::collections::VecDeque;
+
+/// Energy an octopus can hold without flashing.
+pub const DEFAULT_THRESHOLD: u32 = 9;
+
+/// Steps to simulate while looking for a repeated board.
+const MAX_STEPS: usize = 100_000;
+
+/// What happened during one step of the simulation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StepStats {
+    /// Octopuses in the order they flashed.
+    pub order: Vec<Pos>,
+    /// Waves in the longest cascade: 1 if flashes set off no others,
+    /// 0 if nothing flashed.
+    pub depth: usize,
+}
+
+impl StepStats {
+    pub fn flashes(&self) -> usize {
+        self.order.len()
+    }
+}
+
+/// How the simulation evolves in the long run: boards only take finitely
+/// many states, so every simulation eventually repeats.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cycle {
+    /// First step whose board recurs later.
+    pub start: usize,
+    /// Steps between recurrences of the same board.
+    pub period: usize,
+    /// First step on which every octopus flashed, if that ever happens.
+    pub sync: Option<usize>,
+}
+
+impl std::fmt::Display for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "the board repeats every {} step(s) from step {}",
+            self.period, self.start
+        )?;
+        match self.sync {
+            Some(step) => write!(f, "; all octopuses flash at step {}", step),
+            None => write!(f, " and the octopuses never all flash at once"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OctoGrid {
+    board: Grid<u32>,
+    threshold: u32,
+}
+
+impl std::fmt::Display for OctoGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let disp = (0..self.board.rows())
+            .map(|i| self.board.row(i).iter().join(" "))
+            .join("\n");
+        write!(f, "{}", disp)
+    }
+}
+
+impl OctoGrid {
+    /// Reads a rectangular board of energy levels, one digit per octopus.
+    pub fn new(it: &mut impl Iterator<Item = String>) -> Result<Self> {
+        let board = Grid::parse_digits(it).context("Octopus board must be a rectangle of digits")?;
+        ensure!(board.rows() > 0 && board.cols() > 0, "Empty octopus board!");
+        Ok(OctoGrid {
+            board,
+            threshold: DEFAULT_THRESHOLD,
+        })
+    }
+
+    /// Makes octopuses flash once their energy exceeds `threshold`.
+    pub fn with_threshold(mut self, threshold: u32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Advances one step: every octopus gains one energy, then flashes
+    /// cascade outwards from those that crossed the threshold.
+    pub fn step(&mut self) -> StepStats {
+        let mut stats = StepStats::default();
+        let mut queue = VecDeque::new();
+        for pos in self.board.positions() {
+            self.board[pos] = self.board[pos].saturating_add(1);
+            if self.board[pos] > self.threshold {
+                queue.push_back((pos, 1));
+            }
+        }
+        while let Some((pos, wave)) = queue.pop_front() {
+            self.board[pos] = 0;
+            stats.order.push(pos);
+            stats.depth = stats.depth.max(wave);
+            for nbr in self.board.neighbors(pos, Adjacency::EIGHT) {
+                // Zero means the neighbor already flashed this step
+                let energy = self.board[nbr];
+                if energy != 0 {
+                    self.board[nbr] = energy.saturating_add(1);
+                    // Only queue on the increment that crosses the threshold
+                    if energy <= self.threshold && self.board[nbr] > self.threshold {
+                        queue.push_back((nbr, wave + 1));
+                    }
+                }
+            }
+        }
+        stats
+    }
+
+    fn next(&self) -> Self {
+        let mut next = self.clone();
+        next.step();
+        next
+    }
+
+    /// Finds the cycle the simulation falls into using Brent's algorithm,
+    /// giving up if the board hasn't repeated after `max_steps` steps.
+    pub fn find_cycle(&self, max_steps: usize) -> Result<Cycle> {
+        // Find the period: the hare runs ahead of a tortoise that teleports
+        // to it at every power of two. The hare passes every step up to the
+        // end of the first cycle, so it also sees any synchronized flash.
+        let mut sync = None;
+        let (mut power, mut period) = (1, 1);
+        let mut tortoise = self.clone();
+        let mut hare = self.next();
+        let mut steps = 1;
+        loop {
+            if sync.is_none() && hare.all_flashed() {
+                sync = Some(steps);
+            }
+            if tortoise == hare {
+                break;
+            }
+            ensure!(
+                steps < max_steps,
+                "No repeated board within {} steps{}",
+                max_steps,
+                sync.map_or(String::new(), |s| format!(" (all flashed at step {})", s))
+            );
+            if power == period {
+                tortoise = hare.clone();
+                power *= 2;
+                period = 0;
+            }
+            hare = hare.next();
+            period += 1;
+            steps += 1;
+        }
+
+        // Find the start: walk two boards `period` steps apart until they meet
+        let mut tortoise = self.clone();
+        let mut hare = self.clone();
+        for _ in 0..period {
+            hare = hare.next();
+        }
+        let mut start = 0;
+        while tortoise != hare {
+            tortoise = tortoise.next();
+            hare = hare.next();
+            start += 1;
+        }
+        Ok(Cycle {
+            start,
+            period,
+            sync,
+        })
+    }
+
+    /// The board as a heat map: octopuses that just flashed stand out and
+    /// the rest brighten as their energy builds up.
+    pub fn heat_map(&self, color: bool) -> String {
+        if !color {
+            return self.to_string();
+        }
+        (0..self.board.rows())
+            .map(|i| {
+                self.board
+                    .row(i)
+                    .iter()
+                    .map(|&energy| {
+                        let style = match energy {
+                            0 => "1;93".to_string(),
+                            _ => animate::heat(energy, self.threshold),
+                        };
+                        animate::paint(&energy.to_string(), &style, true)
+                    })
+                    .join(" ")
+            })
+            .join("\n")
+    }
+
+    /// The board with brighter cells for higher energy.
+    pub fn picture(&self) -> Picture {
+        Picture::new(
+            self.board
+                .map(|&energy| image::gray(energy, self.threshold)),
+        )
+    }
+
+    pub fn all_flashed(&self) -> bool {
+        self.board.values().all(|&item| item == 0)
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = OctoGrid;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        OctoGrid::new(&mut input.lines().map(String::from))
+    }
+
+    fn part1(grid: &Self::Input) -> Result<Self::Answer1> {
+        let mut grid = grid.clone();
+        Ok((0..100).map(|_| grid.step().flashes()).sum())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<Self::Answer2> {
+        let cycle = grid.find_cycle(MAX_STEPS)?;
+        cycle
+            .sync
+            .with_context(|| format!("No synchronized flash: {}", cycle))
+    }
+
+    fn picture(grid: &Self::Input) -> Result<Option<Picture>> {
+        Ok(Some(grid.picture()))
+    }
+
+    /// Steps up to the synchronized flash, or through the first full cycle
+    /// if there is none.
+    fn frames(grid: &Self::Input, color: bool) -> Option<Frames<'_>> {
+        let last = grid
+            .find_cycle(MAX_STEPS)
+            .map_or(MAX_STEPS, |cycle| cycle.sync.unwrap_or(cycle.start + cycle.period));
+        let mut grid = grid.clone();
+        let first = grid.heat_map(color);
+        let rest = (0..last).map(move |_| {
+            grid.step();
+            grid.heat_map(color)
+        });
+        Some(Box::new(std::iter::once(first).chain(rest)))
+    }
+}
@@ -1,11 +1,11 @@
-use advent::{get_my_lines, iter_lines};
+use crate::Solution;
 use anyhow::{Context, Result};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 
 #[derive(Debug, Default)]
-struct Graph(HashMap<String, HashSet<String>>);
+pub struct Graph(HashMap<String, HashSet<String>>);
 
 impl Deref for Graph {
     type Target = HashMap<String, HashSet<String>>;
@@ -50,18 +50,27 @@ impl Graph {
     }
 }
 
-fn parse_input() -> Result<Graph> {
-    let mut graph: Graph = Graph::default();
-    for line in get_my_lines!() {
-        let (a, b) = line.split('-').next_tuple().context("Failed to split!")?;
-        graph.add_undirected_edge(a, b)
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Graph;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut graph: Graph = Graph::default();
+        for line in input.lines() {
+            let (a, b) = line.split('-').next_tuple().context("Failed to split!")?;
+            graph.add_undirected_edge(a, b)
+        }
+        Ok(graph)
     }
-    Ok(graph)
-}
 
-fn main() -> Result<()> {
-    let g = parse_input()?;
-    println!("Paths (p1): {}", g.count_paths(false));
-    println!("Paths (p2): {}", g.count_paths(true));
-    Ok(())
+    fn part1(graph: &Self::Input) -> Result<Self::Answer1> {
+        Ok(graph.count_paths(false))
+    }
+
+    fn part2(graph: &Self::Input) -> Result<Self::Answer2> {
+        Ok(graph.count_paths(true))
+    }
 }
@@ -0,0 +1,243 @@
+use crate::animate::{self, Frames};
+use crate::grid::Grid;
+use crate::image::{self, Overlay, Picture};
+use crate::ocr::{self, Reading};
+use crate::{spanned_tokens, InputError, Solution};
+use anyhow::{ensure, Context, Result};
+use std::cmp::max;
+use std::collections::HashSet;
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+impl std::fmt::Display for Axis {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Axis::X => write!(f, "x"),
+            Axis::Y => write!(f, "y"),
+        }
+    }
+}
+
+pub type Point = (usize, usize);
+pub type Instruction = (Axis, usize);
+
+/// Parses `x,y`.
+fn parse_dot(s: &str) -> Result<Point, InputError> {
+    let coordinate = |span: Range<usize>| {
+        let text = &s[span.clone()];
+        text.parse()
+            .map_err(|err| InputError::invalid(span, text, "coordinate", err))
+    };
+    let comma = s
+        .find(',')
+        .ok_or_else(|| InputError::unexpected(0..s.len(), s, "a dot like 6,10"))?;
+    Ok((coordinate(0..comma)?, coordinate(comma + 1..s.len())?))
+}
+
+/// Parses `fold along x=N` or `fold along y=N`.
+fn parse_instruction(s: &str) -> Result<Instruction, InputError> {
+    let mut tokens = spanned_tokens(s);
+    let mut end = 0;
+    for keyword in ["fold", "along"] {
+        match tokens.next() {
+            Some((span, token)) if token == keyword => end = span.end,
+            Some((span, token)) => return Err(InputError::unexpected(span, token, "fold along")),
+            None => return Err(InputError::missing(end, "fold along")),
+        }
+    }
+    let (span, fold) = tokens
+        .next()
+        .ok_or_else(|| InputError::missing(end, "a fold line like x=5"))?;
+    let (axis, at) = fold
+        .split_once('=')
+        .ok_or_else(|| InputError::unexpected(span.clone(), fold, "a fold line like x=5"))?;
+    let axis_span = span.start..span.start + axis.len();
+    let axis = match axis {
+        "x" => Axis::X,
+        "y" => Axis::Y,
+        _ => return Err(InputError::unexpected(axis_span, axis, "x or y")),
+    };
+    let at_span = axis_span.end + 1..span.end;
+    let at = at
+        .parse()
+        .map_err(|err| InputError::invalid(at_span, at, "fold position", err))?;
+    if let Some((span, extra)) = tokens.next() {
+        return Err(InputError::unexpected(span, extra, "end of line"));
+    }
+    Ok((axis, at))
+}
+
+/// A transparent sheet of paper with dots on it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Paper {
+    pub dots: HashSet<Point>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Paper {
+    /// A sheet just big enough for all the dots.
+    pub fn new(dots: HashSet<Point>) -> Self {
+        let (width, height) = dots
+            .iter()
+            .fold((0, 0), |acc, p| (max(acc.0, p.0 + 1), max(acc.1, p.1 + 1)));
+        Paper {
+            dots,
+            width,
+            height,
+        }
+    }
+
+    /// Folds the bottom or right part of the paper over the rest. If that
+    /// part is the larger one, it sticks out past the top or left edge,
+    /// which becomes the new origin. A fold past the furthest dot folds up
+    /// an empty part, leaving the dots in place on paper that ends at the line.
+    pub fn fold(&self, (axis, at): Instruction) -> Result<Paper> {
+        let len = match axis {
+            Axis::X => self.width,
+            Axis::Y => self.height,
+        }
+        .max(at.saturating_add(1));
+        let offset = (len - 1).saturating_sub(at).saturating_sub(at);
+        let fold = |v: usize| if v < at { v + offset } else { at * 2 + offset - v };
+        let mut dots = HashSet::new();
+        for &(x, y) in &self.dots {
+            let v = if axis == Axis::X { x } else { y };
+            ensure!(
+                v != at,
+                "Dot {},{} lies on the fold line {}={}",
+                x,
+                y,
+                axis,
+                at
+            );
+            dots.insert(match axis {
+                Axis::X => (fold(x), y),
+                Axis::Y => (x, fold(y)),
+            });
+        }
+        let folded = at + offset;
+        Ok(match axis {
+            Axis::X => Paper {
+                dots,
+                width: folded,
+                height: self.height,
+            },
+            Axis::Y => Paper {
+                dots,
+                width: self.width,
+                height: folded,
+            },
+        })
+    }
+
+    /// Makes every fold in turn.
+    pub fn fold_all(&self, instructions: &[Instruction]) -> Result<Paper> {
+        instructions
+            .iter()
+            .try_fold(self.clone(), |paper, inst| paper.fold(*inst))
+    }
+
+    /// The paper as a grid, lit where there's a dot.
+    pub fn to_grid(&self) -> Grid<bool> {
+        let mut grid = Grid::filled(self.height, self.width, false);
+        for &(x, y) in &self.dots {
+            grid[(y, x)] = true;
+        }
+        grid
+    }
+}
+
+/// Draws the dots, plus the line of the `next` fold if there is one.
+fn draw(paper: &Paper, next: Option<Instruction>, color: bool) -> String {
+    let mut lines = Vec::new();
+    for y in 0..paper.height {
+        let mut line = String::new();
+        for x in 0..paper.width {
+            let cell = match next {
+                _ if paper.dots.contains(&(x, y)) => animate::paint("#", "1;97", color),
+                Some((Axis::X, at)) if x == at => animate::paint("|", "31", color),
+                Some((Axis::Y, at)) if y == at => animate::paint("-", "31", color),
+                _ => " ".to_string(),
+            };
+            line.push_str(&cell);
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (Paper, Vec<Instruction>);
+    type Answer1 = usize;
+    type Answer2 = Reading;
+
+    /// Dots, a blank line, then fold instructions.
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = input.lines().enumerate();
+        let mut dots = HashSet::new();
+        for (idx, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+            dots.insert(parse_dot(line).map_err(|err| err.at_line(idx + 1))?);
+        }
+        let instructions = lines
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(idx, line)| parse_instruction(line).map_err(|err| err.at_line(idx + 1)))
+            .collect::<Result<_, _>>()?;
+        Ok((Paper::new(dots), instructions))
+    }
+
+    fn part1((paper, instructions): &Self::Input) -> Result<Self::Answer1> {
+        let inst = instructions.first().context("No instructions found!")?;
+        Ok(paper.fold(*inst)?.dots.len())
+    }
+
+    fn part2((paper, instructions): &Self::Input) -> Result<Self::Answer2> {
+        let paper = paper.fold_all(instructions)?;
+        ensure!(!paper.dots.is_empty(), "No points!");
+        Ok(ocr::read(&paper.to_grid()))
+    }
+
+    /// The dots left after every fold, in black on white.
+    fn picture((paper, instructions): &Self::Input) -> Result<Option<Picture>> {
+        let dots = paper.fold_all(instructions)?.to_grid();
+        Ok(Some(
+            Picture::new(Grid::filled(dots.rows(), dots.cols(), image::WHITE)).with_overlay(
+                Overlay::Region {
+                    cells: dots.iter().filter(|(_, &dot)| dot).map(|(pos, _)| pos).collect(),
+                    color: image::BLACK,
+                },
+            ),
+        ))
+    }
+
+    /// The paper before each fold, with the fold line drawn, then the result.
+    /// A fold that fails ends the animation with its error.
+    fn frames((paper, instructions): &Self::Input, color: bool) -> Option<Frames<'_>> {
+        let mut paper = paper.clone();
+        let mut next = instructions.iter().copied();
+        let mut done = false;
+        Some(Box::new(std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let inst = next.next();
+            let mut frame = draw(&paper, inst, color);
+            match inst.map(|inst| paper.fold(inst)) {
+                Some(Ok(folded)) => paper = folded,
+                Some(Err(err)) => {
+                    frame += &format!("\n{}", err);
+                    done = true;
+                }
+                None => done = true,
+            }
+            Some(frame)
+        })))
+    }
+}
@@ -1,33 +1,10 @@
-use advent::{get_my_lines, iter_lines};
+use crate::Solution;
 use anyhow::{Context, Result};
 use counter::Counter;
 use itertools::Itertools;
 use std::collections::HashMap;
 
-type Rules = HashMap<(char, char), char>;
-
-fn parse_input() -> Result<(String, Rules)> {
-    let mut rules = HashMap::new();
-    let mut lines = get_my_lines!();
-    let template = lines.next().context("Expected template line.")?;
-    lines.next().context("Expected blank line.")?;
-    for line in lines {
-        let (src, dst) = line
-            .split(" -> ")
-            .next_tuple()
-            .context("Failed to split on ' -> '")?;
-        let src = src
-            .chars()
-            .next_tuple()
-            .context("Expected 2 characters for rule source")?;
-        let dst = dst
-            .chars()
-            .next()
-            .context("Expected 1 character for rule target")?;
-        rules.insert(src, dst);
-    }
-    Ok((template, rules))
-}
+pub type Rules = HashMap<(char, char), char>;
 
 fn pair_insertion(pairs: &Counter<(char, char)>, rules: &Rules) -> Result<Counter<(char, char)>> {
     let mut new_counts = Counter::new();
@@ -69,9 +46,41 @@ fn solve(template: &str, rules: &Rules, times: usize) -> Result<usize> {
     freq_diff(&pair_freqs)
 }
 
-fn main() -> Result<()> {
-    let (template, rules) = parse_input()?;
-    println!("After 10 steps: {}", solve(&template, &rules, 10)?);
-    println!("After 40 steps: {}", solve(&template, &rules, 40)?);
-    Ok(())
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = (String, Rules);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut rules = HashMap::new();
+        let mut lines = input.lines();
+        let template = lines.next().context("Expected template line.")?.to_string();
+        lines.next().context("Expected blank line.")?;
+        for line in lines {
+            let (src, dst) = line
+                .split(" -> ")
+                .next_tuple()
+                .context("Failed to split on ' -> '")?;
+            let src = src
+                .chars()
+                .next_tuple()
+                .context("Expected 2 characters for rule source")?;
+            let dst = dst
+                .chars()
+                .next()
+                .context("Expected 1 character for rule target")?;
+            rules.insert(src, dst);
+        }
+        Ok((template, rules))
+    }
+
+    fn part1((template, rules): &Self::Input) -> Result<Self::Answer1> {
+        solve(template, rules, 10)
+    }
+
+    fn part2((template, rules): &Self::Input) -> Result<Self::Answer2> {
+        solve(template, rules, 40)
+    }
 }
@@ -0,0 +1,180 @@
+use crate::grid::{Adjacency, Grid, Pos};
+use crate::image::{self, Overlay, Picture};
+use crate::{InputError, Solution};
+use anyhow::{Context, Result};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// How `Cavern::find_path` orders its search frontier.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Search {
+    /// Uniform-cost search: expands the cheapest known cell first.
+    Dijkstra,
+    /// Adds the step distance to the goal, a lower bound since every step
+    /// into a cell costs at least 1.
+    AStar,
+}
+
+/// A cheapest route: the cells visited from start to goal, and the total
+/// risk of entering each of them after the start.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Route {
+    pub risk: usize,
+    pub path: Vec<Pos>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Cavern {
+    grid: Grid<u8>,
+    adjacency: Adjacency,
+}
+
+impl std::fmt::Display for Cavern {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+impl Cavern {
+    /// Parses risk levels from 1 to 9; a 0 would break the A* heuristic.
+    pub fn new(it: &mut impl Iterator<Item = String>) -> Result<Self> {
+        let grid: Grid<u8> = Grid::parse_digits(it)?;
+        if let Some(((row, col), _)) = grid.iter().find(|(_, &risk)| risk == 0) {
+            let err = InputError::unexpected(col..col + 1, "0", "a risk level from 1 to 9");
+            return Err(err.at_line(row + 1).into());
+        }
+        Ok(Cavern {
+            grid,
+            adjacency: Adjacency::default(),
+        })
+    }
+
+    /// Sets which cells a path may step between; orthogonal only by default.
+    pub fn with_adjacency(mut self, adjacency: Adjacency) -> Self {
+        self.adjacency = adjacency;
+        self
+    }
+
+    /// The top-left and bottom-right cells.
+    pub fn corners(&self) -> (Pos, Pos) {
+        let (rows, cols) = self.grid.size();
+        ((0, 0), (rows.saturating_sub(1), cols.saturating_sub(1)))
+    }
+
+    pub fn risk_at(&self, pos: Pos) -> Option<u8> {
+        self.grid.get(pos).copied()
+    }
+
+    /// Finds the least risky route from `start` to `goal`, if both are in the cavern.
+    pub fn find_path(&self, start: Pos, goal: Pos, search: Search) -> Option<Route> {
+        if !self.grid.contains(start) || !self.grid.contains(goal) {
+            return None;
+        }
+        let size = self.grid.size();
+        let heuristic = |pos: Pos| match search {
+            Search::Dijkstra => 0,
+            Search::AStar => self.adjacency.distance(pos, goal, size),
+        };
+        let (rows, cols) = size;
+        let mut risk = Grid::filled(rows, cols, usize::MAX);
+        let mut came_from: Grid<Option<Pos>> = Grid::filled(rows, cols, None);
+        let mut frontier = BinaryHeap::new();
+        risk[start] = 0;
+        frontier.push(Reverse((heuristic(start), 0, start)));
+
+        while let Some(Reverse((_, cost, pos))) = frontier.pop() {
+            if pos == goal {
+                return Some(Route {
+                    risk: cost,
+                    path: backtrack(&came_from, goal),
+                });
+            }
+            if cost > risk[pos] {
+                continue; // stale entry, already reached more cheaply
+            }
+            for nbr in self.grid.neighbors(pos, self.adjacency) {
+                let cost_to = cost + self.grid[nbr] as usize;
+                if cost_to < risk[nbr] {
+                    risk[nbr] = cost_to;
+                    came_from[nbr] = Some(pos);
+                    frontier.push(Reverse((cost_to + heuristic(nbr), cost_to, nbr)));
+                }
+            }
+        }
+        None
+    }
+
+    fn lowest_risk(&self) -> Result<usize> {
+        let (start, goal) = self.corners();
+        let route = self
+            .find_path(start, goal, Search::AStar)
+            .context("No path through the cavern!")?;
+        Ok(route.risk)
+    }
+
+    /// Risk levels in gray, darker for safer cells, with `route` drawn on top.
+    pub fn picture(&self, route: Option<&Route>) -> Picture {
+        let picture = Picture::new(self.grid.map(|&risk| image::gray(risk as u32, 9)));
+        match route {
+            Some(route) => picture.with_overlay(Overlay::Path {
+                cells: route.path.clone(),
+                color: image::RED,
+            }),
+            None => picture,
+        }
+    }
+
+    pub fn tile(&mut self, size: usize) {
+        self.grid = self
+            .grid
+            .tile((size, size), |&n, (i, j)| wrapping_add(n, (i + j) as u8));
+    }
+}
+
+fn backtrack(came_from: &Grid<Option<Pos>>, goal: Pos) -> Vec<Pos> {
+    let mut path = vec![goal];
+    let mut pos = goal;
+    while let Some(prev) = came_from[pos] {
+        path.push(prev);
+        pos = prev;
+    }
+    path.reverse();
+    path
+}
+
+fn wrapping_add(num: u8, inc: u8) -> u8 {
+    let mut num = num + inc;
+    while num > 9 {
+        num -= 9;
+    }
+    num
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Cavern;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Cavern::new(&mut input.lines().map(String::from))
+    }
+
+    fn part1(cave: &Self::Input) -> Result<Self::Answer1> {
+        cave.lowest_risk()
+    }
+
+    fn part2(cave: &Self::Input) -> Result<Self::Answer2> {
+        let mut cave = cave.clone();
+        cave.tile(5);
+        cave.lowest_risk()
+    }
+
+    /// The untiled cavern with the safest route from corner to corner.
+    fn picture(cave: &Self::Input) -> Result<Option<Picture>> {
+        let (start, goal) = cave.corners();
+        let route = cave.find_path(start, goal, Search::AStar);
+        Ok(Some(cave.picture(route.as_ref())))
+    }
+}
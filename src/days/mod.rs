@@ -0,0 +1,87 @@
+use crate::animate::Animator;
+use crate::bench::{self, Stats};
+use crate::flags::{Flag, Flags};
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// A registered puzzle: its day number, a function solving both parts,
+/// one timing each stage of the solution, one animating it, one exporting
+/// it as an image, and the flags its report takes with their help and
+/// the function printing that report.
+pub struct Day {
+    pub number: u8,
+    pub solve: fn(&str) -> Result<(String, String)>,
+    pub time: fn(&str, usize) -> Result<[Stats; 3]>,
+    pub animate: fn(&str, &str, &Animator) -> Result<bool>,
+    pub export: fn(&str, &Path) -> Result<()>,
+    pub flags: &'static [Flag],
+    pub help: &'static str,
+    pub report: fn(&str, &Flags) -> Result<()>,
+}
+
+macro_rules! days {
+    ($($number:literal => $module:ident::$solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub const DAYS: &[Day] = &[$(Day {
+            number: $number,
+            solve: crate::solve::<$module::$solution>,
+            time: bench::time::<$module::$solution>,
+            animate: crate::animate::<$module::$solution>,
+            export: crate::export::<$module::$solution>,
+            flags: <$module::$solution as crate::Solution>::FLAGS,
+            help: <$module::$solution as crate::Solution>::HELP,
+            report: crate::report::<$module::$solution>,
+        }),*];
+    };
+}
+
+days! {
+    1 => day01::Day01,
+    2 => day02::Day02,
+    3 => day03::Day03,
+    4 => day04::Day04,
+    5 => day05::Day05,
+    6 => day06::Day06,
+    7 => day07::Day07,
+    8 => day08::Day08,
+    9 => day09::Day09,
+    10 => day10::Day10,
+    11 => day11::Day11,
+    12 => day12::Day12,
+    13 => day13::Day13,
+    14 => day14::Day14,
+    15 => day15::Day15,
+}
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Rejects report flags that some of `days` don't take, or that are given
+/// without any of the flags they only make sense with.
+pub fn check_flags(days: &[&Day], flags: &Flags) -> Result<()> {
+    let spec = |day: &Day, name: &str| day.flags.iter().find(|flag| flag.name == name).copied();
+    for name in flags.names() {
+        for day in days {
+            let Some(spec) = spec(day, name) else {
+                let users: Vec<String> = DAYS
+                    .iter()
+                    .filter(|day| spec(day, name).is_some())
+                    .map(|day| day.number.to_string())
+                    .collect();
+                let plural = if users.len() == 1 { "" } else { "s" };
+                bail!(
+                    "{} only applies to day{} {}",
+                    name,
+                    plural,
+                    users.join(" and ")
+                );
+            };
+            if !spec.needs.is_empty() && !spec.needs.iter().any(|need| flags.has(need)) {
+                bail!("{} needs {}", name, spec.needs.join(" or "));
+            }
+        }
+    }
+    Ok(())
+}
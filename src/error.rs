@@ -0,0 +1,100 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use thiserror::Error;
+
+/// Where in the input a problem was found: an optional 1-based line number
+/// and the byte span of the offending text within that line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Location {
+    pub line: Option<usize>,
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}, ", line)?;
+        }
+        if self.span.len() > 1 {
+            write!(f, "columns {}-{}", self.span.start + 1, self.span.end)
+        } else {
+            write!(f, "column {}", self.span.start + 1)
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("{location}: expected {expected}, found '{text}'")]
+    Unexpected {
+        location: Location,
+        text: String,
+        expected: &'static str,
+    },
+    #[error("{location}: invalid {expected} '{text}'")]
+    Invalid {
+        location: Location,
+        text: String,
+        expected: &'static str,
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
+    #[error("{location}: missing {expected}")]
+    Missing {
+        location: Location,
+        expected: &'static str,
+    },
+}
+
+impl InputError {
+    pub fn unexpected(span: Range<usize>, text: &str, expected: &'static str) -> Self {
+        InputError::Unexpected {
+            location: Location { line: None, span },
+            text: text.to_string(),
+            expected,
+        }
+    }
+
+    pub fn invalid(
+        span: Range<usize>,
+        text: &str,
+        expected: &'static str,
+        source: impl Error + Send + Sync + 'static,
+    ) -> Self {
+        InputError::Invalid {
+            location: Location { line: None, span },
+            text: text.to_string(),
+            expected,
+            source: Box::new(source),
+        }
+    }
+
+    pub fn missing(at: usize, expected: &'static str) -> Self {
+        InputError::Missing {
+            location: Location {
+                line: None,
+                span: at..at,
+            },
+            expected,
+        }
+    }
+
+    pub fn location(&self) -> &Location {
+        match self {
+            InputError::Unexpected { location, .. }
+            | InputError::Invalid { location, .. }
+            | InputError::Missing { location, .. } => location,
+        }
+    }
+
+    /// Attaches the 1-based number of the line the error was found on.
+    pub fn at_line(mut self, line: usize) -> Self {
+        match &mut self {
+            InputError::Unexpected { location, .. }
+            | InputError::Invalid { location, .. }
+            | InputError::Missing { location, .. } => location.line = Some(line),
+        }
+        self
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use std::error::Error;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// An option a day understands on top of the runner's own, for its report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Flag {
+    pub name: &'static str,
+    /// What follows the flag in the usage text, if it takes a value.
+    pub value: Option<&'static str>,
+    /// Flags it only makes sense with, at least one of which must be given.
+    pub needs: &'static [&'static str],
+}
+
+impl Flag {
+    pub const fn switch(name: &'static str) -> Self {
+        Flag {
+            name,
+            value: None,
+            needs: &[],
+        }
+    }
+
+    pub const fn with_value(name: &'static str, value: &'static str) -> Self {
+        Flag {
+            name,
+            value: Some(value),
+            needs: &[],
+        }
+    }
+
+    pub const fn needing(self, needs: &'static [&'static str]) -> Self {
+        Flag { needs, ..self }
+    }
+}
+
+/// How each of `flags` is written in the usage text, like `[--smooth <N>]`.
+pub fn synopsis(flags: &[Flag]) -> Vec<String> {
+    flags
+        .iter()
+        .map(|flag| match flag.value {
+            Some(value) => format!("[{} <{}>]", flag.name, value),
+            None => format!("[{}]", flag.name),
+        })
+        .collect()
+}
+
+/// The day-specific flags given on the command line, in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Flags {
+    given: Vec<(&'static str, Option<String>)>,
+}
+
+impl Flags {
+    pub fn push(&mut self, flag: &Flag, value: Option<String>) {
+        self.given.push((flag.name, value));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.given.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.given.iter().map(|(name, _)| *name)
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.names().any(|given| given == name)
+    }
+
+    /// The value given to `name`, the last one if it was given several times.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.given
+            .iter()
+            .rev()
+            .find(|(given, _)| *given == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// The value given to `name` as a number, or whatever else `T` parses.
+    pub fn parse<T>(&self, name: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        self.value(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .with_context(|| format!("{} needs a number, found '{}'", name, value))
+            })
+            .transpose()
+    }
+
+    /// The range given to `name`, as `parse_range` reads it.
+    pub fn range(&self, name: &str) -> Result<Option<RangeInclusive<usize>>> {
+        self.value(name)
+            .map(|value| parse_range(name, value))
+            .transpose()
+    }
+}
+
+/// Parses the inclusive range given to `flag`, open-ended if the end is left out.
+pub fn parse_range(flag: &str, arg: &str) -> Result<RangeInclusive<usize>> {
+    let parse = |n: &str| {
+        n.parse()
+            .with_context(|| format!("{} needs a range like 10..20, found '{}'", flag, arg))
+    };
+    Ok(match arg.split_once("..") {
+        Some((first, "")) => parse(first)?..=usize::MAX,
+        Some((first, last)) => parse(first)?..=parse(last.trim_start_matches('='))?,
+        None => parse(arg)?..=parse(arg)?,
+    })
+}
@@ -0,0 +1,282 @@
+use crate::InputError;
+use anyhow::{ensure, Result};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A `(row, column)` position in a grid.
+pub type Pos = (usize, usize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left and right.
+    #[default]
+    Four,
+    /// The four orthogonal cells plus the four diagonal ones.
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// Which cells of a `rows x columns` grid count as adjacent to each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Adjacency {
+    pub connectivity: Connectivity,
+    /// Whether stepping off one edge re-enters at the opposite one.
+    pub wrap: bool,
+}
+
+impl Adjacency {
+    pub const FOUR: Adjacency = Adjacency {
+        connectivity: Connectivity::Four,
+        wrap: false,
+    };
+    pub const EIGHT: Adjacency = Adjacency {
+        connectivity: Connectivity::Eight,
+        wrap: false,
+    };
+
+    pub fn wrapping(self) -> Self {
+        Adjacency { wrap: true, ..self }
+    }
+
+    fn step(self, from: usize, delta: isize, len: usize) -> Option<usize> {
+        if self.wrap {
+            Some((from as isize + delta).rem_euclid(len as isize) as usize)
+        } else {
+            from.checked_add_signed(delta).filter(|&to| to < len)
+        }
+    }
+
+    /// The cells adjacent to `pos` in a grid of the given `(rows, columns)`,
+    /// without duplicates and never including `pos` itself.
+    pub fn neighbors(self, pos: Pos, (rows, cols): (usize, usize)) -> Vec<Pos> {
+        let mut nbrs = Vec::new();
+        if pos.0 >= rows || pos.1 >= cols {
+            return nbrs;
+        }
+        for &(di, dj) in self.connectivity.offsets() {
+            let nbr = self.step(pos.0, di, rows).zip(self.step(pos.1, dj, cols));
+            if let Some(nbr) = nbr.filter(|nbr| *nbr != pos && !nbrs.contains(nbr)) {
+                nbrs.push(nbr);
+            }
+        }
+        nbrs
+    }
+
+    /// The fewest steps between two cells, ignoring what's in the grid.
+    pub fn distance(self, a: Pos, b: Pos, (rows, cols): (usize, usize)) -> usize {
+        let axis = |from: usize, to: usize, len: usize| {
+            let direct = from.abs_diff(to);
+            if self.wrap {
+                direct.min(len - direct)
+            } else {
+                direct
+            }
+        };
+        let (di, dj) = (axis(a.0, b.0, rows), axis(a.1, b.1, cols));
+        match self.connectivity {
+            Connectivity::Four => di + dj,
+            Connectivity::Eight => di.max(dj),
+        }
+    }
+}
+
+/// A rectangular grid of cells stored in row-major order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, rejecting rows of differing lengths.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self> {
+        let mut grid = Grid {
+            cells: Vec::new(),
+            rows: 0,
+            cols: 0,
+        };
+        for row in rows {
+            if grid.rows == 0 {
+                grid.cols = row.len();
+            }
+            ensure!(
+                row.len() == grid.cols,
+                "Row {} has {} cells, expected {}",
+                grid.rows + 1,
+                row.len(),
+                grid.cols
+            );
+            grid.cells.extend(row);
+            grid.rows += 1;
+        }
+        Ok(grid)
+    }
+
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The `(rows, columns)` of the grid.
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.rows && pos.1 < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.cols + pos.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.cols.max(1))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |idx| (idx / cols, idx % cols))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn neighbors(&self, pos: Pos, adjacency: Adjacency) -> impl Iterator<Item = Pos> {
+        adjacency.neighbors(pos, self.size()).into_iter()
+    }
+
+    pub fn map_in_place(&mut self, f: impl FnMut(&mut T)) {
+        self.cells.iter_mut().for_each(f);
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Repeats the grid `down x across` times; `f` derives each copied cell
+    /// from the original and the `(row, column)` of the tile it lands in.
+    pub fn tile(&self, (down, across): (usize, usize), f: impl Fn(&T, Pos) -> T) -> Grid<T> {
+        let (rows, cols) = (self.rows * down, self.cols * across);
+        let cells = (0..rows * cols)
+            .map(|idx| {
+                let (i, j) = (idx / cols, idx % cols);
+                let tile = (i / self.rows, j / self.cols);
+                f(&self[(i % self.rows, j % self.cols)], tile)
+            })
+            .collect();
+        Grid { cells, rows, cols }
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    /// Parses lines of decimal digits, one cell per digit.
+    pub fn parse_digits(lines: impl IntoIterator<Item = impl AsRef<str>>) -> Result<Self> {
+        let rows = lines
+            .into_iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                line.as_ref()
+                    .char_indices()
+                    .map(|(col, c)| match c.to_digit(10) {
+                        Some(digit) => Ok(T::from(digit as u8)),
+                        None => Err(InputError::unexpected(
+                            col..col + c.len_utf8(),
+                            &c.to_string(),
+                            "a digit",
+                        )
+                        .at_line(idx + 1)),
+                    })
+                    .collect::<Result<Vec<T>, InputError>>()
+            })
+            .collect::<Result<Vec<Vec<T>>, InputError>>()?;
+        Grid::from_rows(rows)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{:?} is outside the grid", pos);
+        &self.cells[pos.0 * self.cols + pos.1]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{:?} is outside the grid", pos);
+        &mut self.cells[pos.0 * self.cols + pos.1]
+    }
+}
+
+/// Writes one line per row, with no separator between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(row) {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+use crate::grid::{Grid, Pos};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// A color as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const RED: Rgb = [220, 30, 30];
+
+const PALETTE: [Rgb; 8] = [
+    [230, 25, 75],
+    [60, 180, 75],
+    [0, 130, 200],
+    [245, 130, 48],
+    [145, 30, 180],
+    [70, 240, 240],
+    [240, 50, 230],
+    [210, 245, 60],
+];
+
+/// Pixels per grid cell unless a picture asks for something else.
+pub const DEFAULT_SCALE: usize = 8;
+
+/// A shade of gray for `value` out of `max`, from black to white.
+pub fn gray(value: u32, max: u32) -> Rgb {
+    let level = (value.min(max) * 255 / max.max(1)) as u8;
+    [level; 3]
+}
+
+/// One of a handful of distinct colors, cycling through them by `idx`.
+pub fn palette(idx: usize) -> Rgb {
+    PALETTE[idx % PALETTE.len()]
+}
+
+/// The file formats a picture can be written as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    /// Picks the format from the file extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let ext = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+        Ok(match ext.to_ascii_lowercase().as_str() {
+            "ppm" => Format::Ppm,
+            "png" => Format::Png,
+            "svg" => Format::Svg,
+            _ => bail!(
+                "Can't export to {}: expected a .ppm, .png or .svg file",
+                path.display()
+            ),
+        })
+    }
+}
+
+/// Cells highlighted on top of a picture's base grid.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Overlay {
+    /// Cells filled with one color, such as a basin or a set of dots.
+    Region { cells: Vec<Pos>, color: Rgb },
+    /// A route through the grid; SVG draws it as a line through cell centers.
+    Path { cells: Vec<Pos>, color: Rgb },
+}
+
+/// A grid of colored cells with overlays, ready to be written out as an image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Picture {
+    base: Grid<Rgb>,
+    overlays: Vec<Overlay>,
+    scale: usize,
+}
+
+impl Picture {
+    pub fn new(base: Grid<Rgb>) -> Self {
+        Picture {
+            base,
+            overlays: Vec::new(),
+            scale: DEFAULT_SCALE,
+        }
+    }
+
+    /// Draws `overlay` over the base grid and any earlier overlays.
+    pub fn with_overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// Makes every cell `scale x scale` pixels.
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// The color of every cell once the overlays are painted on.
+    pub fn cells(&self) -> Grid<Rgb> {
+        self.painted(true)
+    }
+
+    /// The base grid with the regions painted on, and the paths too if `paths`.
+    fn painted(&self, paths: bool) -> Grid<Rgb> {
+        let mut cells = self.base.clone();
+        for overlay in &self.overlays {
+            let (covered, color) = match overlay {
+                Overlay::Region { cells, color } => (cells, color),
+                Overlay::Path { cells, color } if paths => (cells, color),
+                Overlay::Path { .. } => continue,
+            };
+            for &pos in covered {
+                if let Some(cell) = cells.get_mut(pos) {
+                    *cell = *color;
+                }
+            }
+        }
+        cells
+    }
+
+    /// Width and height in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.base.cols() * self.scale, self.base.rows() * self.scale)
+    }
+
+    /// The pixels row by row, three bytes each.
+    fn pixel_rows(&self) -> Vec<Vec<u8>> {
+        let cells = self.cells();
+        let mut rows = Vec::new();
+        for row in 0..cells.rows() {
+            let line: Vec<u8> = cells
+                .row(row)
+                .iter()
+                .flat_map(|rgb| std::iter::repeat_n(rgb, self.scale))
+                .flatten()
+                .copied()
+                .collect();
+            rows.extend(std::iter::repeat_n(line, self.scale));
+        }
+        rows
+    }
+
+    /// A binary (P6) portable pixmap.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (width, height) = self.dimensions();
+        let mut out = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        out.extend(self.pixel_rows().concat());
+        out
+    }
+
+    /// An uncompressed 8-bit RGB PNG.
+    pub fn to_png(&self) -> Vec<u8> {
+        let (width, height) = self.dimensions();
+        let mut header = Vec::new();
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        // Bit depth 8, truecolor, default compression, filter and interlace
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Every scanline starts with filter type 0 (none)
+        let mut raw = Vec::new();
+        for row in self.pixel_rows() {
+            raw.push(0);
+            raw.extend(row);
+        }
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut out, b"IHDR", &header);
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    /// An SVG with one square per cell, measured in cells and sized in pixels.
+    pub fn to_svg(&self) -> String {
+        let (width, height) = self.dimensions();
+        let hex = |[r, g, b]: Rgb| format!("#{:02x}{:02x}{:02x}", r, g, b);
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+            width,
+            height,
+            self.base.cols(),
+            self.base.rows()
+        );
+        let cells = self.painted(false);
+        for ((row, col), &rgb) in cells.iter() {
+            out += &format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>\n",
+                col,
+                row,
+                hex(rgb)
+            );
+        }
+        for overlay in &self.overlays {
+            if let Overlay::Path { cells, color } = overlay {
+                let points: Vec<String> = cells
+                    .iter()
+                    .map(|(row, col)| format!("{}.5,{}.5", col, row))
+                    .collect();
+                out += &format!(
+                    "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.3\" \
+                     stroke-linejoin=\"round\" shape-rendering=\"auto\"/>\n",
+                    points.join(" "),
+                    hex(*color)
+                );
+            }
+        }
+        out + "</svg>\n"
+    }
+
+    /// Writes the picture in the format its extension names.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let bytes = match Format::from_path(path)? {
+            Format::Ppm => self.to_ppm(),
+            Format::Png => self.to_png(),
+            Format::Svg => self.to_svg().into_bytes(),
+        };
+        fs::write(path, bytes).with_context(|| format!("Couldn't write {}", path.display()))
+    }
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of stored (uncompressed) deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate, 32K window, no preset dictionary, fastest compression
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
@@ -1,56 +1,226 @@
-use anyhow::{Context, Result};
-use std::error::Error;
-use std::fmt;
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::fmt::Display;
 use std::fs::{read_to_string, File};
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
-pub fn iter_lines(filename: &str) -> impl Iterator<Item = String> {
-    let file = File::open(filename).expect("File not found!");
-    let reader = BufReader::new(file);
-    reader.lines().map(|line| line.unwrap())
+pub mod animate;
+pub mod bench;
+pub mod days;
+mod error;
+pub mod flags;
+pub mod grid;
+pub mod image;
+pub mod ocr;
+pub mod sonar;
+pub mod table;
+
+use animate::{Animator, Frames};
+pub use error::{InputError, Location};
+use flags::{Flag, Flags};
+pub use grid::Grid;
+use image::Picture;
+
+/// A puzzle solution: parses the raw input once and solves both parts from it.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// Renders each step of the day's simulation, if it has one worth watching.
+    fn frames(_input: &Self::Input, _color: bool) -> Option<Frames<'_>> {
+        None
+    }
+
+    /// Draws the day's grid with whatever the solution found on top of it.
+    fn picture(_input: &Self::Input) -> Result<Option<Picture>> {
+        Ok(None)
+    }
+
+    /// Options for analyses beyond the two answers, which `report` prints.
+    const FLAGS: &'static [Flag] = &[];
+
+    /// What `FLAGS` do, as a paragraph of the usage text.
+    const HELP: &'static str = "";
+
+    /// Prints the analyses `flags` ask for, after the answers.
+    fn report(_input: &Self::Input, _flags: &Flags) -> Result<()> {
+        Ok(())
+    }
 }
 
-pub fn iter_csv_line(filename: &str) -> Result<Vec<String>> {
-    Ok(read_to_string(&filename)
-        .with_context(|| format!("Couldn't read input from {}", filename))?
-        .split(',')
-        .map(String::from)
-        .collect())
+/// Parses `input` and solves both parts, rendering the answers as strings.
+pub fn solve<S: Solution>(input: &str) -> Result<(String, String)> {
+    let parsed = S::parse(input)?;
+    Ok((
+        S::part1(&parsed)?.to_string(),
+        S::part2(&parsed)?.to_string(),
+    ))
 }
 
-#[macro_export]
-macro_rules! get_my_lines {
-    () => {
-        iter_lines(&file!().replace("bin", "input").replace(".rs", ".txt"))
+/// Parses `input` and plays the day's simulation through `animator`;
+/// `false` if the day has no simulation to play.
+pub fn animate<S: Solution>(input: &str, title: &str, animator: &Animator) -> Result<bool> {
+    let parsed = S::parse(input)?;
+    let Some(frames) = S::frames(&parsed, animator.color()) else {
+        return Ok(false);
     };
+    animator.play(title, frames)?;
+    Ok(true)
 }
 
-#[macro_export]
-macro_rules! get_my_values {
-    () => {
-        iter_csv_line(&file!().replace("bin", "input").replace(".rs", ".txt"))
-    };
+/// Parses `input` and prints the day's report, unless no flags ask for one.
+pub fn report<S: Solution>(input: &str, flags: &Flags) -> Result<()> {
+    if flags.is_empty() {
+        return Ok(());
+    }
+    S::report(&S::parse(input)?, flags)
 }
 
-#[derive(Debug, Clone)]
-pub struct InputError;
+/// Parses `input` and writes the day's picture to `path`.
+pub fn export<S: Solution>(input: &str, path: &Path) -> Result<()> {
+    let parsed = S::parse(input)?;
+    S::picture(&parsed)?
+        .context("Nothing to export")?
+        .save(path)
+}
 
-impl fmt::Display for InputError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Failed to parse input!")
+fn numbered_lines(reader: impl BufRead, name: String) -> impl Iterator<Item = Result<String>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(move |(idx, line)| line.with_context(|| format!("Couldn't read {}:{}", name, idx + 1)))
+}
+
+fn join_lines(lines: impl Iterator<Item = Result<String>>) -> Result<String> {
+    let mut text = String::new();
+    for line in lines {
+        text.push_str(&line?);
+        text.push('\n');
     }
+    Ok(text)
+}
+
+/// Iterates over the lines of a file; errors name the file and line number.
+pub fn iter_lines(filename: impl AsRef<Path>) -> Result<impl Iterator<Item = Result<String>>> {
+    let filename = filename.as_ref();
+    let file = File::open(filename)
+        .with_context(|| format!("Couldn't open input {}", filename.display()))?;
+    Ok(numbered_lines(
+        BufReader::new(file),
+        filename.display().to_string(),
+    ))
+}
+
+/// Iterates over the lines of standard input, like `iter_lines`.
+pub fn iter_stdin_lines() -> impl Iterator<Item = Result<String>> {
+    numbered_lines(io::stdin().lock(), String::from("<stdin>"))
+}
+
+/// Reads a whole file through `iter_lines`, normalizing line endings to `\n`.
+pub fn read_lines(filename: impl AsRef<Path>) -> Result<String> {
+    join_lines(iter_lines(filename)?)
+}
+
+pub fn iter_csv_line(filename: impl AsRef<Path>) -> Result<Vec<String>> {
+    let filename = filename.as_ref();
+    Ok(read_to_string(filename)
+        .with_context(|| format!("Couldn't read input from {}", filename.display()))?
+        .split(',')
+        .map(String::from)
+        .collect())
+}
+
+/// Environment variable naming a directory of `dayNN.txt` inputs.
+pub const INPUT_DIR_VAR: &str = "ADVENT_INPUT_DIR";
+
+/// Finds puzzle inputs independently of the working directory.
+///
+/// Candidate directories are tried in order: the one given on the command
+/// line (`--input`), then `$ADVENT_INPUT_DIR`, then `src/input` under the
+/// crate root.
+#[derive(Debug, Default)]
+pub struct InputLocator {
+    dir: Option<PathBuf>,
 }
 
-impl Error for InputError {}
+impl InputLocator {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        InputLocator { dir }
+    }
+
+    fn candidates(&self, day: u8) -> Vec<PathBuf> {
+        let filename = format!("day{:02}.txt", day);
+        let mut dirs: Vec<PathBuf> = self.dir.iter().cloned().collect();
+        dirs.extend(env::var_os(INPUT_DIR_VAR).map(PathBuf::from));
+        dirs.push(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src")
+                .join("input"),
+        );
+        dirs.into_iter().map(|dir| dir.join(&filename)).collect()
+    }
 
-impl From<regex::Error> for InputError {
-    fn from(_: regex::Error) -> InputError {
-        InputError
+    pub fn locate(&self, day: u8) -> Result<PathBuf> {
+        let candidates = self.candidates(day);
+        if let Some(path) = candidates.iter().find(|path| path.is_file()) {
+            return Ok(path.clone());
+        }
+        let tried: Vec<String> = candidates
+            .iter()
+            .map(|path| format!("  {}", path.display()))
+            .collect();
+        bail!(
+            "No input found for day {}, tried:\n{}",
+            day,
+            tried.join("\n")
+        )
+    }
+
+    pub fn read(&self, day: u8) -> Result<String> {
+        read_lines(self.locate(day)?)
     }
 }
 
-impl From<std::num::ParseIntError> for InputError {
-    fn from(_: std::num::ParseIntError) -> InputError {
-        InputError
+/// Where a day's puzzle input is read from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum InputSource {
+    /// The day's `dayNN.txt`, found by an [`InputLocator`].
+    #[default]
+    Located,
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Interprets a command-line argument: `-` is stdin, anything else a path.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self, day: u8, locator: &InputLocator) -> Result<String> {
+        match self {
+            InputSource::Located => locator.read(day),
+            InputSource::Stdin => join_lines(iter_stdin_lines()),
+            InputSource::File(path) => read_lines(path),
+        }
     }
 }
+
+/// Splits `s` on whitespace, yielding each token with its byte span in `s`.
+pub fn spanned_tokens(s: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    s.split_whitespace().map(move |token| {
+        // Tokens are subslices of `s`, so their offset is the pointer difference
+        let start = token.as_ptr() as usize - s.as_ptr() as usize;
+        (start..start + token.len(), token)
+    })
+}
@@ -1,3 +1,276 @@
-fn main() {
-    println!("Hello, world!");
+use advent::animate::Animator;
+use advent::bench::{self, DayTimings, STAGES};
+use advent::days::{self, Day, DAYS};
+use advent::flags::{self, parse_range, Flags};
+use advent::{InputLocator, InputSource};
+use anyhow::{bail, ensure, Context, Result};
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const USAGE: &str = "Usage: advent run [--input <DIR>] <DAY>... | --all
+       advent run <DAY> <FILE>|-
+       advent run --animate [--delay <MS>] [--steps <RANGE>] <DAY>...
+       advent run --export <IMAGE> <DAY>";
+
+const BENCH_USAGE: &str = "       advent bench [--runs <N>] [--save <FILE>] [--baseline <FILE>]
+                    [--threshold <PCT>] [--input <DIR>] <DAY>... | --all";
+
+const HELP: &str = "DAY is a single day (5) or an inclusive range (1..15 or 1..=15).
+Inputs are read from DIR/dayNN.txt, then $ADVENT_INPUT_DIR/dayNN.txt,
+then src/input/dayNN.txt in the crate. Giving a FILE, or - for stdin,
+overrides this for a single day.
+
+--animate plays the simulation of days 6, 11 and 13 before solving them,
+waiting MS milliseconds between frames (default 100). RANGE picks the
+frames to show, e.g. 10..20 or 50.. (step 0 is the initial state). When
+stdout isn't a terminal the frames are printed one after another.
+
+--export draws the grid of day 9, 11, 13 or 15 with its basins, folded
+dots or safest path on top, as a .ppm, .png or .svg file.";
+
+const BENCH_HELP: &str = "bench times parsing and each part separately over N runs (default 10).
+--save writes the timings as a JSON baseline; --baseline compares against
+one and fails if a stage's minimum time grew by more than PCT (default 10).";
+
+/// The usage text, with a line and a paragraph for each day that takes
+/// flags of its own.
+fn usage() -> String {
+    const INDENT: &str = "       advent run ";
+    let mut usage = USAGE.to_string();
+    let mut help = HELP.to_string();
+    for day in DAYS.iter().filter(|day| !day.flags.is_empty()) {
+        let mut line = INDENT.to_string();
+        for flag in flags::synopsis(day.flags) {
+            if line.len() + flag.len() > 78 && line.len() > INDENT.len() {
+                usage += &format!("\n{}", line.trim_end());
+                line = " ".repeat(INDENT.len());
+            }
+            line += &flag;
+            line += " ";
+        }
+        usage += &format!("\n{}{}", line, day.number);
+        if !day.help.is_empty() {
+            help += &format!("\n\n{}", day.help);
+        }
+    }
+    format!("{}\n{}\n\n{}\n\n{}", usage, BENCH_USAGE, help, BENCH_HELP)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Command {
+    Run,
+    Bench,
+}
+
+#[derive(Default)]
+struct Options {
+    days: Vec<&'static Day>,
+    source: InputSource,
+    input_dir: Option<PathBuf>,
+    runs: usize,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    animate: Option<Animator>,
+    export: Option<PathBuf>,
+    /// Options for the days' own reports.
+    flags: Flags,
+}
+
+/// Parses a day or a range of days; `None` if `arg` isn't numeric at all.
+fn parse_days(arg: &str) -> Option<Result<Vec<&'static Day>>> {
+    let (first, last) = match arg.split_once("..") {
+        Some((first, last)) => (first, last.trim_start_matches('=')),
+        None => (arg, arg),
+    };
+    let (first, last): (u8, u8) = (first.parse().ok()?, last.parse().ok()?);
+    let days = (first..=last)
+        .map(|n| days::find(n).with_context(|| format!("Day {} is not solved yet", n)))
+        .collect();
+    Some(days)
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<(Command, Options)> {
+    let command = match args.next().as_deref() {
+        Some("run") => Command::Run,
+        Some("bench") => Command::Bench,
+        _ => bail!(usage()),
+    };
+    let mut opts = Options {
+        runs: 10,
+        threshold: 10.0,
+        ..Options::default()
+    };
+    let mut all = false;
+    let (mut delay, mut steps) = (Duration::from_millis(100), 0..=usize::MAX);
+    let mut animate = false;
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .with_context(|| format!("{} needs a value", flag))
+        };
+        match (command, flag) {
+            (_, "--all") => all = true,
+            (_, "--input") => opts.input_dir = Some(value()?.into()),
+            (Command::Run, "--animate") => animate = true,
+            (Command::Run, "--delay") => {
+                delay = Duration::from_millis(value()?.parse().context("--delay needs a number")?)
+            }
+            (Command::Run, "--steps") => steps = parse_range(flag, &value()?)?,
+            (Command::Run, "--export") => opts.export = Some(value()?.into()),
+            (Command::Bench, "--runs") => {
+                opts.runs = value()?.parse().context("--runs needs a number")?
+            }
+            (Command::Bench, "--save") => opts.save = Some(value()?.into()),
+            (Command::Bench, "--baseline") => opts.baseline = Some(value()?.into()),
+            (Command::Bench, "--threshold") => {
+                opts.threshold = value()?.parse().context("--threshold needs a number")?
+            }
+            (Command::Run, flag) if flag.starts_with("--") => {
+                let spec = DAYS
+                    .iter()
+                    .flat_map(|day| day.flags)
+                    .find(|spec| spec.name == flag)
+                    .with_context(|| format!("Unknown option {}\n\n{}", flag, usage()))?;
+                let value = if spec.value.is_some() {
+                    Some(value()?)
+                } else {
+                    ensure!(inline.is_none(), "{} doesn't take a value", flag);
+                    None
+                };
+                opts.flags.push(spec, value);
+            }
+            (_, flag) if flag.starts_with("--") => bail!("Unknown option {}\n\n{}", flag, usage()),
+            _ => match parse_days(&arg) {
+                Some(days) => opts.days.extend(days?),
+                None if opts.source == InputSource::Located => {
+                    opts.source = InputSource::from_arg(&arg)
+                }
+                None => bail!("Only one input file can be given, found '{}'", arg),
+            },
+        }
+    }
+    if all {
+        opts.days = DAYS.iter().collect();
+    } else if opts.days.is_empty() {
+        bail!("No days given!\n\n{}", usage());
+    }
+    if opts.source != InputSource::Located && opts.days.len() != 1 {
+        bail!("An input file can only be given for a single day");
+    }
+    if opts.export.is_some() && opts.days.len() != 1 {
+        bail!("An image can only be exported for a single day");
+    }
+    days::check_flags(&opts.days, &opts.flags)?;
+    if animate {
+        opts.animate = Some(Animator::new(delay, steps));
+    }
+    Ok((command, opts))
+}
+
+fn run(day: &Day, input: &str) -> Result<()> {
+    println!("Day {:02}", day.number);
+    let (part1, part2) =
+        (day.solve)(input).with_context(|| format!("Failed to solve day {}", day.number))?;
+    println!("  Part 1: {}", part1);
+    println!("  Part 2: {}", part2);
+    Ok(())
+}
+
+fn bench(opts: &Options, locator: &InputLocator) -> Result<()> {
+    let baseline = match &opts.baseline {
+        Some(path) => bench::load_baseline(path)?,
+        None => Vec::new(),
+    };
+    let mut timings = Vec::new();
+    let mut regressions = 0;
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "Day", "Stage", "Mean", "Min", "Base min", "Change"
+    );
+    for day in &opts.days {
+        let input = opts.source.read(day.number, locator)?;
+        let stages = (day.time)(&input, opts.runs)
+            .with_context(|| format!("Failed to solve day {}", day.number))?;
+        let base = baseline.iter().find(|b| b.day == day.number);
+        for (idx, stats) in stages.iter().enumerate() {
+            let mut line = format!(
+                "{:>3}  {:<5}  {:>10.1?}  {:>10.1?}",
+                day.number,
+                STAGES[idx],
+                stats.mean(),
+                stats.min()
+            );
+            if let Some(base) = base.map(|b| &b.stages[idx]) {
+                let change = bench::change(stats, base) * 100.0;
+                line += &format!("  {:>10.1?}  {:>+7.1}%", base.min(), change);
+                if change > opts.threshold {
+                    regressions += 1;
+                    line += "  REGRESSION";
+                }
+            }
+            println!("{}", line);
+        }
+        timings.push(DayTimings {
+            day: day.number,
+            stages,
+        });
+    }
+    if let Some(path) = &opts.save {
+        bench::save_baseline(path, &timings)?;
+        println!("Saved baseline to {}", path.display());
+    }
+    if regressions > 0 {
+        bail!(
+            "{} stage(s) slower than the baseline by more than {}%",
+            regressions,
+            opts.threshold
+        );
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let (command, opts) = parse_args(env::args().skip(1))?;
+    let locator = InputLocator::new(opts.input_dir.clone());
+    match command {
+        Command::Run => {
+            for day in &opts.days {
+                let input = opts.source.read(day.number, &locator)?;
+                if let Some(animator) = &opts.animate {
+                    let title = format!("Day {:02}", day.number);
+                    let animated = (day.animate)(&input, &title, animator)
+                        .with_context(|| format!("Failed to animate day {}", day.number))?;
+                    if !animated {
+                        ensure!(
+                            opts.days.len() > 1,
+                            "Day {} has nothing to animate",
+                            day.number
+                        );
+                        println!("Day {:02} has nothing to animate, skipping", day.number);
+                    }
+                }
+                if let Some(path) = &opts.export {
+                    (day.export)(&input, path)
+                        .with_context(|| format!("Failed to export day {}", day.number))?;
+                    println!("Saved day {} to {}", day.number, path.display());
+                }
+                // A report can still explain input the answers fail on, such as
+                // a day 2 route that flies under a looser --surface
+                let solved = run(day, &input);
+                (day.report)(&input, &opts.flags)
+                    .with_context(|| format!("Failed to analyze day {}", day.number))?;
+                solved?;
+            }
+            Ok(())
+        }
+        Command::Bench => bench(&opts, &locator),
+    }
 }
@@ -0,0 +1,124 @@
+use crate::grid::Grid;
+use std::fmt;
+use std::ops::Range;
+
+/// Rows in a letter of the font.
+pub const GLYPH_HEIGHT: usize = 6;
+/// Columns in a letter, not counting the blank column after it.
+pub const GLYPH_WIDTH: usize = 4;
+
+/// The capital letters of the 4x6 font Advent of Code draws answers in.
+const FONT: &[(char, [&str; GLYPH_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// A letter-sized block of the image that isn't in the font.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownGlyph {
+    /// Which letter of the text it stands in for, counting from 0.
+    pub index: usize,
+    /// The image columns the letter and its trailing gap cover.
+    pub columns: Range<usize>,
+    /// The block as `#` and `.` rows.
+    pub art: String,
+}
+
+/// Text read from an image, with `?` in place of every unknown glyph. It
+/// displays as the text, followed by a drawing of each unknown glyph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reading {
+    pub text: String,
+    pub unknown: Vec<UnknownGlyph>,
+}
+
+impl fmt::Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)?;
+        if !self.unknown.is_empty() {
+            let columns: Vec<String> = self
+                .unknown
+                .iter()
+                .map(|glyph| format!("{}-{}", glyph.columns.start, glyph.columns.end - 1))
+                .collect();
+            let plural = if columns.len() == 1 { "" } else { "s" };
+            write!(
+                f,
+                " (unknown glyph{} at columns {})",
+                plural,
+                columns.join(", ")
+            )?;
+        }
+        // Draw what couldn't be read so the answer can still be made out
+        for (idx, glyph) in self.unknown.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            let drawing = glyph.art.replace('.', " ");
+            let lines: Vec<&str> = drawing.lines().map(str::trim_end).collect();
+            let end = lines
+                .iter()
+                .rposition(|line| !line.is_empty())
+                .map_or(0, |i| i + 1);
+            for line in &lines[..end] {
+                write!(f, "\n{}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads capital letters drawn left to right from the top-left corner, each
+/// followed by a blank column. A block is unknown if it doesn't match the
+/// font exactly, including when anything is drawn in its gap or below it.
+pub fn read(image: &Grid<bool>) -> Reading {
+    let lit = |row, col| image.get((row, col)).copied().unwrap_or(false);
+    let pitch = GLYPH_WIDTH + 1;
+    let rows = image.rows().max(GLYPH_HEIGHT);
+    let mut reading = Reading {
+        text: String::new(),
+        unknown: Vec::new(),
+    };
+    for (index, start) in (0..image.cols()).step_by(pitch).enumerate() {
+        let art: Vec<String> = (0..rows)
+            .map(|row| {
+                (start..start + pitch)
+                    .map(|col| if lit(row, col) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let letter = FONT.iter().find(|(_, glyph)| {
+            art.iter().enumerate().all(|(row, line)| {
+                let want = glyph.get(row).copied().unwrap_or("....");
+                line.starts_with(want) && line.ends_with('.')
+            })
+        });
+        match letter {
+            Some((letter, _)) => reading.text.push(*letter),
+            None => {
+                reading.text.push('?');
+                reading.unknown.push(UnknownGlyph {
+                    index,
+                    columns: start..(start + pitch).min(image.cols()),
+                    art: art.join("\n"),
+                });
+            }
+        }
+    }
+    reading
+}
@@ -0,0 +1,298 @@
+use crate::table::Table;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::{Range, RangeInclusive};
+
+/// How the sum of a window compares to the sum one depth earlier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Change {
+    Increase,
+    Decrease,
+    Plateau,
+}
+
+/// The last `size` depths, with their sum kept up to date as it slides.
+#[derive(Clone, Debug)]
+pub struct Window {
+    values: VecDeque<u32>,
+    size: usize,
+    sum: u64,
+}
+
+impl Window {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "Window size must be at least 1");
+        Window {
+            values: VecDeque::new(),
+            size,
+            sum: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The sum of the window once it holds `size` depths.
+    pub fn sum(&self) -> Option<u64> {
+        (self.values.len() == self.size).then_some(self.sum)
+    }
+
+    /// Slides the window on to `value`, returning how its sum changed if it
+    /// was already full.
+    pub fn push(&mut self, value: u32) -> Option<Change> {
+        self.values.push_back(value);
+        self.sum += value as u64;
+        if self.values.len() <= self.size {
+            return None;
+        }
+        let dropped = self.values.pop_front()?;
+        self.sum -= dropped as u64;
+        // Both windows share every value but these two
+        Some(match value.cmp(&dropped) {
+            Ordering::Greater => Change::Increase,
+            Ordering::Less => Change::Decrease,
+            Ordering::Equal => Change::Plateau,
+        })
+    }
+}
+
+/// How often each kind of change happened during a sweep.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub increases: usize,
+    pub decreases: usize,
+    pub plateaus: usize,
+}
+
+impl Tally {
+    pub fn count(&self, change: Change) -> usize {
+        match change {
+            Change::Increase => self.increases,
+            Change::Decrease => self.decreases,
+            Change::Plateau => self.plateaus,
+        }
+    }
+
+    fn add(&mut self, change: Change) {
+        match change {
+            Change::Increase => self.increases += 1,
+            Change::Decrease => self.decreases += 1,
+            Change::Plateau => self.plateaus += 1,
+        }
+    }
+}
+
+/// Slides a window of `size` depths over `depths`, tallying how its sum changes.
+pub fn windowed_sweep(depths: &[u32], size: usize) -> Tally {
+    let mut window = Window::new(size);
+    let mut tally = Tally::default();
+    for &depth in depths {
+        if let Some(change) = window.push(depth) {
+            tally.add(change);
+        }
+    }
+    tally
+}
+
+/// Sweeps every window size in `sizes`, leaving out those longer than the
+/// input.
+pub fn sweep_sizes(depths: &[u32], sizes: RangeInclusive<usize>) -> Vec<(usize, Tally)> {
+    let (first, last) = ((*sizes.start()).max(1), (*sizes.end()).min(depths.len()));
+    (first..=last)
+        .map(|size| (size, windowed_sweep(depths, size)))
+        .collect()
+}
+
+pub fn sweep_table(sweeps: &[(usize, Tally)]) -> Table {
+    let mut table = Table::new(["Window", "Increases", "Decreases", "Plateaus"]);
+    for (size, tally) in sweeps {
+        table.push([size, &tally.increases, &tally.decreases, &tally.plateaus]);
+    }
+    table
+}
+
+/// The longest stretch of depths, each deeper than the one before it.
+pub fn longest_increasing_run(depths: &[u32]) -> Option<Range<usize>> {
+    let mut window = Window::new(1);
+    let (mut best, mut start) = (None::<Range<usize>>, 0);
+    for (idx, &depth) in depths.iter().enumerate() {
+        if window.push(depth) != Some(Change::Increase) {
+            start = idx;
+        }
+        if best
+            .as_ref()
+            .is_none_or(|best| idx + 1 - start > best.len())
+        {
+            best = Some(start..idx + 1);
+        }
+    }
+    best
+}
+
+/// The biggest rise towards the surface between one depth and the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DepthDrop {
+    /// Index of the shallower depth the drop ends on.
+    pub index: usize,
+    pub from: u32,
+    pub to: u32,
+}
+
+impl DepthDrop {
+    pub fn size(&self) -> u32 {
+        self.from - self.to
+    }
+}
+
+pub fn largest_drop(depths: &[u32]) -> Option<DepthDrop> {
+    let mut window = Window::new(1);
+    let mut best: Option<DepthDrop> = None;
+    let mut previous = None;
+    for (index, &depth) in depths.iter().enumerate() {
+        if let (Some(Change::Decrease), Some(from)) = (window.push(depth), previous) {
+            let drop = DepthDrop {
+                index,
+                from,
+                to: depth,
+            };
+            if best.is_none_or(|best| drop.size() > best.size()) {
+                best = Some(drop);
+            }
+        }
+        previous = Some(depth);
+    }
+    best
+}
+
+/// The mean of each window of `size` depths, at the index of the window's
+/// last depth; `None` until the first window fills up.
+pub fn smooth(depths: &[u32], size: usize) -> Vec<Option<f64>> {
+    let mut window = Window::new(size);
+    depths
+        .iter()
+        .map(|&depth| {
+            window.push(depth);
+            window.sum().map(|sum| sum as f64 / size as f64)
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Extremum {
+    /// Deeper than the values on either side.
+    Maximum,
+    /// Shallower than the values on either side.
+    Minimum,
+}
+
+/// Local extrema of a series, skipping its gaps. A level stretch counts
+/// once, at its first index, if both of its neighbors are on the same side.
+pub fn extrema(series: &[Option<f64>]) -> Vec<(usize, Extremum)> {
+    let points: Vec<(usize, f64)> = series
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, value)| value.map(|value| (idx, value)))
+        .collect();
+    // Collapse level stretches to their first point
+    let mut levels: Vec<(usize, f64)> = Vec::new();
+    for point in points {
+        if levels.last().is_none_or(|last| last.1 != point.1) {
+            levels.push(point);
+        }
+    }
+    levels
+        .windows(3)
+        .filter_map(|w| {
+            let (before, (idx, value), after) = (w[0].1, w[1], w[2].1);
+            if value > before && value > after {
+                Some((idx, Extremum::Maximum))
+            } else if value < before && value < after {
+                Some((idx, Extremum::Minimum))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The shape of a sonar sweep: its longest descent, sharpest rise and the
+/// turning points of its smoothed depths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub depths: Vec<u32>,
+    /// Size of the window the depths are averaged over.
+    pub smoothing: usize,
+    pub run: Option<Range<usize>>,
+    pub drop: Option<DepthDrop>,
+    pub smoothed: Vec<Option<f64>>,
+    pub extrema: Vec<(usize, Extremum)>,
+}
+
+impl Profile {
+    pub fn new(depths: &[u32], smoothing: usize) -> Self {
+        let smoothed = smooth(depths, smoothing);
+        Profile {
+            depths: depths.to_vec(),
+            smoothing,
+            run: longest_increasing_run(depths),
+            drop: largest_drop(depths),
+            extrema: extrema(&smoothed),
+            smoothed,
+        }
+    }
+
+    fn count(&self, kind: Extremum) -> usize {
+        self.extrema.iter().filter(|(_, e)| *e == kind).count()
+    }
+
+    /// One row per statistic, with where in the sweep it was found.
+    pub fn summary(&self) -> Table {
+        let mut table = Table::new(["Statistic", "Value", "Where"]);
+        if let Some(run) = &self.run {
+            let at = format!("{}..={}", run.start, run.end - 1);
+            table.push([
+                "Longest increasing run".to_string(),
+                run.len().to_string(),
+                at,
+            ]);
+        }
+        if let Some(drop) = &self.drop {
+            let at = format!("{} -> {} at {}", drop.from, drop.to, drop.index);
+            table.push(["Largest drop".to_string(), drop.size().to_string(), at]);
+        }
+        let smoothing = format!("window of {}", self.smoothing);
+        for (name, kind) in [
+            ("Local maxima", Extremum::Maximum),
+            ("Local minima", Extremum::Minimum),
+        ] {
+            table.push([
+                name.to_string(),
+                self.count(kind).to_string(),
+                smoothing.clone(),
+            ]);
+        }
+        table
+    }
+
+    /// Every depth with its smoothed value and whether it's a turning point.
+    pub fn series(&self) -> Table {
+        let mut table = Table::new(["Index", "Depth", "Smoothed", "Extremum"]);
+        let mut extrema = self.extrema.iter().peekable();
+        for (idx, (depth, smoothed)) in self.depths.iter().zip(&self.smoothed).enumerate() {
+            let extremum = match extrema.next_if(|(at, _)| *at == idx) {
+                Some((_, Extremum::Maximum)) => "max",
+                Some((_, Extremum::Minimum)) => "min",
+                None => "",
+            };
+            let smoothed = smoothed.map_or(String::new(), |value| format!("{:.2}", value));
+            table.push([
+                idx.to_string(),
+                depth.to_string(),
+                smoothed,
+                extremum.to_string(),
+            ]);
+        }
+        table
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::io::{self, Write};
+
+/// Rows of values under a header, printed with right-aligned columns.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: impl IntoIterator<Item = impl ToString>) -> Self {
+        Table {
+            headers: headers.into_iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: impl IntoIterator<Item = impl ToString>) {
+        self.rows
+            .push(row.into_iter().map(|cell| cell.to_string()).collect());
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Prints the table aligned, or as comma-separated values if `csv`.
+    pub fn print(&self, csv: bool) {
+        if csv {
+            print!("{}", self.to_csv());
+        } else {
+            println!("{}", self);
+        }
+    }
+
+    /// The header and rows as comma-separated values, quoting cells that
+    /// need it.
+    pub fn to_csv(&self) -> String {
+        std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|row| csv_line(row))
+            .collect()
+    }
+
+    /// Writes rows as they come rather than holding them all: line by line
+    /// if `csv`, otherwise aligned in tables of at most `page` rows, each
+    /// under its own header.
+    pub fn stream(
+        out: &mut impl Write,
+        headers: impl IntoIterator<Item = impl ToString>,
+        rows: impl IntoIterator<Item = Vec<String>>,
+        csv: bool,
+        page: usize,
+    ) -> io::Result<()> {
+        let mut table = Table::new(headers);
+        if csv {
+            out.write_all(csv_line(&table.headers).as_bytes())?;
+            for row in rows {
+                out.write_all(csv_line(&row).as_bytes())?;
+            }
+            return Ok(());
+        }
+        for row in rows {
+            // Only a full page is written here, so the last one never ends up empty
+            if table.rows.len() == page.max(1) {
+                writeln!(out, "{}\n", table)?;
+                table.rows.clear();
+            }
+            table.rows.push(row);
+        }
+        writeln!(out, "{}", table)
+    }
+}
+
+fn csv_line(row: &[String]) -> String {
+    let quote = |cell: &String| {
+        if cell.contains([',', '"', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.clone()
+        }
+    };
+    row.iter().map(quote).collect::<Vec<_>>().join(",") + "\n"
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut widths: Vec<usize> = self.headers.iter().map(String::len).collect();
+        for row in &self.rows {
+            for (col, cell) in row.iter().enumerate() {
+                match widths.get_mut(col) {
+                    Some(width) => *width = (*width).max(cell.len()),
+                    None => widths.push(cell.len()),
+                }
+            }
+        }
+        for (idx, row) in std::iter::once(&self.headers).chain(&self.rows).enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:>1$}", cell, width))
+                .collect();
+            write!(f, "{}", cells.join("  "))?;
+        }
+        Ok(())
+    }
+}
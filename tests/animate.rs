@@ -0,0 +1,54 @@
+use advent::animate::{heat, paint, Animator};
+use advent::days::{day01::Day01, day06::Day06, day11::Day11, day13::Day13};
+use advent::Solution;
+use std::time::Duration;
+
+mod common;
+
+fn example_frames<S: Solution>(color: bool) -> Vec<String> {
+    S::frames(&common::example::<S>(), color)
+        .expect("day has an animation")
+        .collect()
+}
+
+#[test]
+fn paint_only_in_color() {
+    assert_eq!(paint("#", "31", false), "#");
+    assert_eq!(paint("#", "31", true), "\x1b[31m#\x1b[0m");
+    assert_eq!(heat(0, 9), "38;5;236");
+    assert_eq!(heat(12, 9), "38;5;255");
+}
+
+#[test]
+fn octopuses_until_synchronized() {
+    let frames = example_frames::<Day11>(false);
+    assert_eq!(frames.len(), 196);
+    assert!(frames[0].starts_with("5 4 8 3 1 4 3 2 2 3"));
+    assert!(frames[195].split_whitespace().all(|cell| cell == "0"));
+    assert!(example_frames::<Day11>(true)[195].contains("\x1b[1;93m0"));
+}
+
+#[test]
+fn paper_before_each_fold() {
+    let frames = example_frames::<Day13>(false);
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].lines().nth(7), Some("-----------"));
+    assert_eq!(frames[1].lines().next(), Some("# ## |#  # "));
+    assert_eq!(frames[2], "#####\n#   #\n#   #\n#   #\n#####\n     \n     ");
+}
+
+#[test]
+fn fish_every_day() {
+    let frames = example_frames::<Day06>(false);
+    assert_eq!(frames.len(), 257);
+    assert!(frames[0].ends_with("Total: 5"));
+    assert!(frames[256].ends_with("Total: 26984457539"));
+    assert!(!frames[0].contains('\x1b'));
+}
+
+#[test]
+fn days_without_simulation_are_skipped() {
+    let animator = Animator::new(Duration::ZERO, 0..=0);
+    let animated = advent::animate::<Day01>("199\n200\n", "Day 01", &animator).unwrap();
+    assert!(!animated);
+}
@@ -0,0 +1,37 @@
+//! Fixtures shared by the integration tests; each test crate uses only some.
+#![allow(dead_code)]
+
+use advent::{read_lines, InputError, Solution};
+use std::any;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+/// The example from the puzzle text for `day`, in tests/examples.
+pub fn example_path(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("tests/examples/day{:02}.txt", day))
+}
+
+pub fn example_text(day: u8) -> String {
+    read_lines(example_path(day)).unwrap()
+}
+
+/// The parsed example of the day `S` solves, found from its `DayNN` name.
+pub fn example<S: Solution>() -> S::Input {
+    let name = any::type_name::<S>().rsplit("::").next().unwrap();
+    let day = name
+        .strip_prefix("Day")
+        .and_then(|day| day.parse().ok())
+        .unwrap_or_else(|| panic!("{} isn't named after its day", name));
+    S::parse(&example_text(day)).unwrap()
+}
+
+pub fn parse_error<S>(input: &str) -> InputError
+where
+    S: Solution,
+    S::Input: Debug,
+{
+    S::parse(input)
+        .expect_err("input should be rejected")
+        .downcast::<InputError>()
+        .expect("error should be an InputError")
+}
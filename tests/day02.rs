@@ -0,0 +1,265 @@
+use advent::days::day02::{
+    find_model, tokenize, Aimed, Day02, Heading, Move, Plain, Position, Script, Step, Surface,
+    Waypoint, DEFAULT_MAX_MOVES,
+};
+use advent::{InputError, Solution};
+
+fn script(input: &str) -> Script {
+    Day02::parse(input).unwrap()
+}
+
+#[test]
+fn rejects_surfacing_by_default() {
+    let route = script("down 2\nforward 1\nup 5\n");
+    let err = route.run(&Plain, Surface::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Line 3: up 5: the submarine would be 3 above the surface"
+    );
+}
+
+#[test]
+fn clamps_or_allows_negative_depth() {
+    let route = script("down 2\nup 5\ndown 1\n");
+    let clamped = route.run(&Plain, Surface::Clamp).unwrap();
+    assert_eq!(clamped.depth, 1);
+    let allowed = route.run(&Plain, Surface::Allow).unwrap();
+    assert_eq!(allowed.depth, -2);
+}
+
+#[test]
+fn parses_surface_names() {
+    assert_eq!("clamp".parse::<Surface>().unwrap(), Surface::Clamp);
+    assert_eq!("allow".parse::<Surface>().unwrap(), Surface::Allow);
+    assert_eq!(
+        "float".parse::<Surface>().unwrap_err().to_string(),
+        "Unknown surface 'float', expected reject, clamp or allow"
+    );
+}
+
+#[test]
+fn aims_upwards_without_limit() {
+    // Negative aim is fine until a forward move climbs past the surface
+    let route = script("down 3\nforward 2\nup 5\nforward 1\nforward 5\n");
+    let err = route.run(&Aimed, Surface::Reject).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Line 5: forward 5: the submarine would be 6 above the surface"
+    );
+    let pos = route.run(&Aimed, Surface::Allow).unwrap();
+    assert_eq!((pos.x, pos.depth, pos.aim), (8, -6, -2));
+}
+
+#[test]
+fn reports_overflow() {
+    let start = Position {
+        x: i64::MAX - 1,
+        ..Position::new()
+    };
+    let err = start
+        .execute(Move::Forward(2), &Plain, Surface::Reject)
+        .unwrap_err();
+    assert_eq!(err.to_string(), format!("{} + 2 overflows", i64::MAX - 1));
+    let deep = Position {
+        aim: i64::MAX / 2,
+        ..Position::new()
+    };
+    assert!(deep
+        .execute(Move::Forward(3), &Aimed, Surface::Reject)
+        .is_err());
+}
+
+#[test]
+fn tokenizes_braces_and_comments() {
+    let tokens = tokenize("repeat 2 {forward 1}  # twice\n# nothing\nturn");
+    let texts: Vec<(usize, &str)> = tokens.iter().map(|t| (t.line, t.text)).collect();
+    assert_eq!(
+        texts,
+        [
+            (1, "repeat"),
+            (1, "2"),
+            (1, "{"),
+            (1, "forward"),
+            (1, "1"),
+            (1, "}"),
+            (3, "turn")
+        ]
+    );
+    assert_eq!(tokens[3].span, 10..17);
+}
+
+#[test]
+fn unrolls_nested_repeats() {
+    let route =
+        script("repeat 2 {\n  forward 1\n  repeat 3 { down 1 }\n  repeat 0 { up 9 }\n}\nturn\n");
+    let steps: Vec<Step> = route.steps().collect();
+    assert_eq!(steps.len(), 9);
+    assert_eq!(
+        steps[..4],
+        [
+            Step {
+                line: 2,
+                action: Move::Forward(1)
+            },
+            Step {
+                line: 3,
+                action: Move::Down(1)
+            },
+            Step {
+                line: 3,
+                action: Move::Down(1)
+            },
+            Step {
+                line: 3,
+                action: Move::Down(1)
+            },
+        ]
+    );
+    assert_eq!(
+        steps[8],
+        Step {
+            line: 6,
+            action: Move::Turn
+        }
+    );
+    let pos = route.run(&Plain, Surface::Reject).unwrap();
+    assert_eq!((pos.x, pos.depth, pos.heading), (2, 6, Heading::Astern));
+}
+
+#[test]
+fn turning_reverses_forward_and_back() {
+    let route = script("forward 5\nturn\nforward 2\nback 4\n");
+    assert_eq!(route.run(&Plain, Surface::Reject).unwrap().x, 7);
+    let aimed = script("down 1\nforward 4\nturn\nforward 2\nback 1\n");
+    let pos = aimed.run(&Aimed, Surface::Reject).unwrap();
+    // Turning changes where x goes, not which way the nose dives
+    assert_eq!((pos.x, pos.depth), (3, 5));
+}
+
+#[test]
+fn finds_models_by_name() {
+    assert_eq!(find_model("plain").map(|m| m.name()), Some("plain"));
+    assert_eq!(find_model("aimed").map(|m| m.name()), Some("aimed"));
+    assert!(find_model("sideways").is_none());
+}
+
+#[test]
+fn reports_unclosed_and_stray_braces() {
+    let err = |input: &str| {
+        input
+            .parse::<Script>()
+            .expect_err("script should be rejected")
+    };
+    assert_eq!(
+        err("down 1\nrepeat 2 {\n  up 1\n").to_string(),
+        "line 2, column 11: missing a closing }"
+    );
+    assert!(matches!(
+        err("forward 1 }"),
+        InputError::Unexpected { text, .. } if text == "}"
+    ));
+    assert_eq!(
+        err("repeat\n{ }").to_string(),
+        "line 1, column 7: missing a repeat count"
+    );
+}
+
+const EXAMPLE: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+
+#[test]
+fn traces_every_move() {
+    let route = script(EXAMPLE);
+    let trace: Vec<Waypoint> = route
+        .trace(&Aimed, Surface::Reject)
+        .collect::<anyhow::Result<_>>()
+        .unwrap();
+    assert_eq!(trace.len(), 6);
+    assert_eq!(trace[2].index, 3);
+    assert_eq!(trace[2].step.action, Move::Forward(8));
+    assert_eq!(
+        (trace[2].pos.x, trace[2].pos.depth, trace[2].pos.aim),
+        (13, 40, 5)
+    );
+    assert_eq!(trace[5].pos, route.run(&Aimed, Surface::Reject).unwrap());
+}
+
+#[test]
+fn trace_ends_at_first_failure() {
+    let route = script("down 1\nup 2\ndown 5\n");
+    let mut trace = route.trace(&Plain, Surface::Reject);
+    assert!(trace.next().unwrap().is_ok());
+    let err = trace.next().unwrap().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Line 2: up 2: the submarine would be 1 above the surface"
+    );
+    assert!(trace.next().is_none());
+}
+
+#[test]
+fn replays_any_prefix() {
+    let route = script(EXAMPLE);
+    let after = |moves| route.position_after(moves, &Plain, Surface::Reject);
+    assert_eq!(after(0).unwrap(), Position::new());
+    assert_eq!(after(3).unwrap().to_string(), "x 13, depth 5, aim 0");
+    assert_eq!(
+        after(6).unwrap(),
+        route.run(&Plain, Surface::Reject).unwrap()
+    );
+    assert_eq!(
+        after(7).unwrap_err().to_string(),
+        "The route only has 6 moves"
+    );
+    // A failure before the prefix ends is reported rather than overlooked
+    let surfacing = script("up 1\nforward 1\n");
+    assert!(surfacing
+        .position_after(2, &Plain, Surface::Reject)
+        .is_err());
+}
+
+#[test]
+fn counts_repeated_moves_in_prefixes() {
+    let route = script("turn\nrepeat 3 { forward 2 }\n");
+    let pos = route.position_after(3, &Plain, Surface::Reject).unwrap();
+    assert_eq!(pos.to_string(), "x -4, depth 0, aim 0, turned around");
+}
+
+#[test]
+fn stops_runaway_repeats() {
+    let route = script("down 1\nrepeat 4000000000 {\n  repeat 4000000000 { forward 1 }\n}\n");
+    let err = route.run(&Plain, Surface::Reject).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Line 3: the script makes more than {} moves",
+            DEFAULT_MAX_MOVES
+        )
+    );
+    // Prefixes within the limit can still be asked about
+    let pos = route.position_after(6, &Plain, Surface::Reject).unwrap();
+    assert_eq!((pos.x, pos.depth), (5, 1));
+
+    let limited = script("repeat 3 { forward 1 }").with_max_moves(2);
+    assert!(limited.run(&Plain, Surface::Reject).is_err());
+    assert_eq!(
+        limited
+            .with_max_moves(3)
+            .run(&Plain, Surface::Reject)
+            .unwrap()
+            .x,
+        3
+    );
+}
+
+#[test]
+fn skips_repeats_without_moves() {
+    let route =
+        script("repeat 4000000000 { repeat 4000000000 { repeat 0 { up 1 } } }\nforward 2\n");
+    assert_eq!(route.steps().count(), 1);
+    assert_eq!(route.run(&Plain, Surface::Reject).unwrap().x, 2);
+}
+
+#[test]
+fn help_states_the_move_limit() {
+    assert!(Day02::HELP.contains(&DEFAULT_MAX_MOVES.to_string()));
+}
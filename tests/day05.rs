@@ -0,0 +1,153 @@
+use advent::days::day05::{count_overlaps, parse_vents, Backend, Day05, Slopes, Vent};
+use advent::{InputLocator, Solution};
+
+const BACKENDS: [Backend; 4] = [
+    Backend::Sparse,
+    Backend::Dense,
+    Backend::Intersections,
+    Backend::Auto,
+];
+
+fn parse(input: &str) -> Vec<Vent> {
+    Day05::parse(input).unwrap()
+}
+
+fn counts(vents: &[Vent]) -> Vec<usize> {
+    BACKENDS
+        .iter()
+        .map(|&backend| count_overlaps(vents, backend).unwrap())
+        .collect()
+}
+
+#[test]
+fn backends_agree_on_input() {
+    let vents = parse(&InputLocator::default().read(5).unwrap());
+    let straight: Vec<Vent> = vents.iter().filter(|v| v.is_straight()).cloned().collect();
+    assert_eq!(counts(&straight), vec![4826; 4]);
+    assert_eq!(counts(&vents), vec![16793; 4]);
+}
+
+#[test]
+fn collinear_overlaps_count_once() {
+    // Three vents along y = 2 overlapping on x = 2..=6, crossed at x = 4
+    // by a vertical vent and at x = 8 by a diagonal one
+    let vents = parse("0,2 -> 6,2\n2,2 -> 9,2\n6,2 -> 2,2\n4,0 -> 4,5\n6,0 -> 9,3\n");
+    assert_eq!(counts(&vents), vec![6; 4]);
+    // Overlaps along x = 4 and y = 2 share their crossing
+    let vents = parse("0,2 -> 6,2\n2,2 -> 9,2\n4,0 -> 4,5\n4,1 -> 4,3\n");
+    assert_eq!(counts(&vents), vec![7; 4]);
+}
+
+#[test]
+fn diagonals_between_lattice_points_dont_cross() {
+    let vents = parse("0,0 -> 3,3\n0,1 -> 1,0\n");
+    assert_eq!(counts(&vents), vec![0; 4]);
+}
+
+#[test]
+fn huge_coordinates() {
+    let vents = parse(
+        "0,0 -> 1000000000,1000000000\n\
+         0,1000000000 -> 1000000000,0\n\
+         0,500000000 -> 1000000000,500000000\n\
+         -5,7 -> 999999999,7\n\
+         -1000000000,7 -> 100,7\n",
+    );
+    // The three long lines meet in one point, the two at y = 7 overlap on
+    // x = -5..=100 (where the first diagonal crosses them) and the second
+    // diagonal crosses the longer one at x = 999999993
+    let expected = 1 + 106 + 1;
+    assert_eq!(
+        count_overlaps(&vents, Backend::Intersections).unwrap(),
+        expected
+    );
+    assert_eq!(count_overlaps(&vents, Backend::Auto).unwrap(), expected);
+
+    // Spanning every i32 on both axes, the two lines only cross at the origin
+    let vents = parse(
+        "-2147483648,0 -> 2147483647,0\n\
+         0,-2147483648 -> 0,2147483647\n",
+    );
+    assert_eq!(count_overlaps(&vents, Backend::Auto).unwrap(), 1);
+}
+
+#[test]
+fn backends_agree_on_random_vents() {
+    let mut seed: u64 = 0x2021_0005;
+    let mut next = |n: u64| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) % n) as i32
+    };
+    for _ in 0..50 {
+        let mut input = String::new();
+        for _ in 0..40 {
+            let (x, y, len) = (next(20), next(20), next(12));
+            let (dx, dy) = [(1, 0), (0, 1), (1, 1), (1, -1), (-1, 0)][next(5) as usize];
+            let line = format!("{},{} -> {},{}\n", x, y, x + dx * len, y + dy * len);
+            input.push_str(&line);
+        }
+        let counts = counts(&parse(&input));
+        assert!(
+            counts.iter().all(|&c| c == counts[0]),
+            "{:?} on\n{}",
+            counts,
+            input
+        );
+    }
+}
+
+#[test]
+fn iterating_keeps_endpoints() {
+    let vent: Vent = "3,4 -> 1,2".parse().unwrap();
+    assert_eq!(
+        vent.points().collect::<Vec<_>>(),
+        vec![(3, 4), (2, 3), (1, 2)]
+    );
+    assert_eq!((&vent).into_iter().len(), 3);
+    assert_eq!((vent.start(), vent.end()), ((3, 4), (1, 2)));
+
+    let dot: Vent = "5,5 -> 5,5".parse().unwrap();
+    assert_eq!(dot.points().collect::<Vec<_>>(), vec![(5, 5)]);
+}
+
+#[test]
+fn rasterizes_any_slope() {
+    let vent = Vent::new((0, 0), (4, 2));
+    let cells: Vec<_> = vent.points().collect();
+    assert_eq!(cells, vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+    // The same cells when drawn the other way round
+    let mut back: Vec<_> = Vent::new((4, 2), (0, 0)).points().collect();
+    back.reverse();
+    assert_eq!(back, cells);
+
+    let steep: Vec<_> = Vent::new((1, -3), (0, 3)).points().collect();
+    assert_eq!(steep.len(), 7);
+    assert_eq!(steep.first(), Some(&(1, -3)));
+    assert_eq!(steep.last(), Some(&(0, 3)));
+    assert!(steep.windows(2).all(|w| w[1].1 == w[0].1 + 1));
+}
+
+#[test]
+fn slope_policies() {
+    let input = "0,0 -> 4,0\n0,0 -> 4,2\n4,4 -> 0,0\n";
+    let err = parse_vents(input, Slopes::Reject).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Line 2: vent 0,0 -> 4,2 is neither horizontal, vertical nor diagonal"
+    );
+    assert!(Day05::parse(input).is_err());
+    assert_eq!(parse_vents(input, Slopes::Skip).unwrap().len(), 2);
+
+    let vents = parse_vents(input, Slopes::Rasterize).unwrap();
+    // The sloped vent shares (0,0) with both others, (1,1) with the
+    // diagonal and (2,1) with nothing
+    assert_eq!(count_overlaps(&vents, Backend::Sparse).unwrap(), 2);
+    assert_eq!(count_overlaps(&vents, Backend::Dense).unwrap(), 2);
+    assert_eq!(count_overlaps(&vents, Backend::Auto).unwrap(), 2);
+    let err = count_overlaps(&vents, Backend::Intersections).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Can't intersect sloped vent 0,0 -> 4,2"));
+}
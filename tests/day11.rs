@@ -0,0 +1,88 @@
+use advent::days::day11::{Cycle, OctoGrid};
+
+mod common;
+
+fn grid(s: &str) -> anyhow::Result<OctoGrid> {
+    OctoGrid::new(&mut s.lines().map(String::from))
+}
+
+#[test]
+fn steps_non_square_board() {
+    let mut octopuses = grid("11111\n19991\n19991\n11111").unwrap();
+    let stats = octopuses.step();
+    assert_eq!(stats.flashes(), 6);
+    assert_eq!(stats.depth, 1);
+    assert_eq!(
+        octopuses.to_string(),
+        "3 4 5 4 3\n4 0 0 0 4\n4 0 0 0 4\n3 4 5 4 3"
+    );
+}
+
+#[test]
+fn rejects_ragged_board() {
+    let err = grid("123\n45\n").unwrap_err();
+    assert!(format!("{:#}", err).contains("Row 2 has 2 cells, expected 3"));
+    assert!(grid("").is_err());
+}
+
+#[test]
+fn configurable_threshold() {
+    let mut octopuses = grid("000").unwrap().with_threshold(1);
+    assert_eq!(octopuses.step().flashes(), 0);
+    assert_eq!(octopuses.step().flashes(), 3);
+    assert!(octopuses.all_flashed());
+
+    let mut octopuses = grid("000").unwrap().with_threshold(0);
+    assert_eq!(octopuses.step().flashes(), 3);
+
+    // Nothing can exceed the largest threshold, so nothing ever flashes
+    let mut octopuses = grid("99\n99").unwrap().with_threshold(u32::MAX);
+    assert_eq!(octopuses.step().flashes(), 0);
+}
+
+#[test]
+fn cascade_statistics() {
+    let mut octopuses = grid("11111\n19991\n19191\n19991\n11111").unwrap();
+    let stats = octopuses.step();
+    assert_eq!(stats.flashes(), 9);
+    assert_eq!(stats.depth, 2);
+    assert_eq!(stats.order[..3], [(1, 1), (1, 2), (1, 3)]);
+    assert_eq!(stats.order.last(), Some(&(2, 2)));
+    assert_eq!(
+        octopuses.to_string(),
+        "3 4 5 4 3\n4 0 0 0 4\n5 0 0 0 5\n4 0 0 0 4\n3 4 5 4 3"
+    );
+
+    let stats = octopuses.step();
+    assert_eq!(stats, Default::default());
+}
+
+#[test]
+fn cycle_with_synchronized_flash() {
+    let octopuses = grid(&common::example_text(11)).unwrap();
+    let cycle = octopuses.find_cycle(1000).unwrap();
+    assert_eq!(cycle.sync, Some(195));
+    assert_eq!(cycle.period, 10);
+    assert!(cycle.start <= 195);
+
+    let err = octopuses.find_cycle(50).unwrap_err();
+    assert_eq!(err.to_string(), "No repeated board within 50 steps");
+}
+
+#[test]
+fn cycle_without_synchronized_flash() {
+    let octopuses = grid("00\n33").unwrap().with_threshold(3);
+    let cycle = octopuses.find_cycle(1000).unwrap();
+    assert_eq!(
+        cycle,
+        Cycle {
+            start: 0,
+            period: 2,
+            sync: None
+        }
+    );
+    assert_eq!(
+        cycle.to_string(),
+        "the board repeats every 2 step(s) from step 0 and the octopuses never all flash at once"
+    );
+}
@@ -0,0 +1,120 @@
+use advent::days::day13::{Axis, Day13, Paper};
+use advent::{InputError, Location, Solution};
+use std::collections::HashSet;
+
+mod common;
+
+use common::parse_error;
+
+fn paper(dots: &[(usize, usize)]) -> Paper {
+    Paper::new(dots.iter().copied().collect())
+}
+
+#[test]
+fn parses_dots_then_folds() {
+    let (paper, folds) = Day13::parse("6,10\n0,14\n\nfold along y=7\nfold along x=5\n").unwrap();
+    assert_eq!((paper.width, paper.height), (7, 15));
+    assert_eq!(folds, vec![(Axis::Y, 7), (Axis::X, 5)]);
+}
+
+#[test]
+fn rejects_bad_instructions() {
+    let err = parse_error::<Day13>("1,1\n\nfold along |=3");
+    assert_eq!(
+        err.location(),
+        &Location {
+            line: Some(3),
+            span: 11..12
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "line 3, column 12: expected x or y, found '|'"
+    );
+
+    let err = parse_error::<Day13>("1,1\n\nfold along x=3\nfold over y=2");
+    assert_eq!(
+        err.to_string(),
+        "line 4, columns 6-9: expected fold along, found 'over'"
+    );
+    let err = parse_error::<Day13>("1,1\n\nfold along y3");
+    assert_eq!(
+        err.to_string(),
+        "line 3, columns 12-13: expected a fold line like x=5, found 'y3'"
+    );
+    let err = parse_error::<Day13>("1,1\n\nfold along x=-3");
+    assert!(matches!(&err, InputError::Invalid { text, .. } if text == "-3"));
+    assert_eq!(err.location().span, 13..15);
+    let err = parse_error::<Day13>("1,1\n\nfold along x=3 now");
+    assert_eq!(
+        err.to_string(),
+        "line 3, columns 16-18: expected end of line, found 'now'"
+    );
+    let err = parse_error::<Day13>("1,1\n\nfold along");
+    assert_eq!(
+        err.to_string(),
+        "line 3, column 11: missing a fold line like x=5"
+    );
+}
+
+#[test]
+fn rejects_bad_dots() {
+    let err = parse_error::<Day13>("1,1\n2;3\n\nfold along x=1");
+    assert_eq!(
+        err.to_string(),
+        "line 2, columns 1-3: expected a dot like 6,10, found '2;3'"
+    );
+    let err = parse_error::<Day13>("1,1\n2,b\n");
+    assert!(
+        matches!(&err, InputError::Invalid { text, expected: "coordinate", .. } if text == "b")
+    );
+    assert_eq!(err.location().span, 2..3);
+}
+
+#[test]
+fn rejects_dot_on_fold_line() {
+    let err = paper(&[(0, 0), (3, 1), (6, 2)])
+        .fold((Axis::X, 3))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Dot 3,1 lies on the fold line x=3");
+}
+
+#[test]
+fn fold_past_the_dots_trims_an_empty_half() {
+    let folded = paper(&[(0, 0), (1, 2)]).fold((Axis::Y, 3)).unwrap();
+    assert_eq!(folded.dots, HashSet::from([(0, 0), (1, 2)]));
+    assert_eq!((folded.width, folded.height), (2, 3));
+    let folded = paper(&[(0, 0), (1, 4)]).fold((Axis::Y, 5)).unwrap();
+    assert_eq!((folded.width, folded.height), (2, 5));
+    let folded = paper(&[(0, 0), (1, 2)]).fold((Axis::X, 7)).unwrap();
+    assert_eq!((folded.width, folded.height), (7, 3));
+    let folded = paper(&[(0, 0), (1, 2)])
+        .fold((Axis::X, usize::MAX))
+        .unwrap();
+    assert_eq!(folded.width, usize::MAX);
+
+    let (paper, folds) = Day13::parse("0,0\n1,2\n\nfold along y=3\n").unwrap();
+    assert_eq!(paper.fold(folds[0]).unwrap().dots.len(), 2);
+}
+
+#[test]
+fn centered_fold_keeps_origin() {
+    let folded = paper(&[(0, 0), (4, 1), (3, 2)]).fold((Axis::X, 2)).unwrap();
+    assert_eq!(folded.dots, HashSet::from([(0, 0), (0, 1), (1, 2)]));
+    assert_eq!((folded.width, folded.height), (2, 3));
+}
+
+#[test]
+fn off_center_fold_shifts_coordinates() {
+    // Folding a 7-wide sheet at x=1 flips five columns over one: the far
+    // edge at x=6 becomes the new left edge
+    let folded = paper(&[(0, 0), (2, 0), (6, 1)]).fold((Axis::X, 1)).unwrap();
+    assert_eq!((folded.width, folded.height), (5, 2));
+    assert_eq!(folded.dots, HashSet::from([(4, 0), (0, 1)]));
+
+    let err = paper(&[(0, 0), (0, 3)]).fold((Axis::Y, 0)).unwrap_err();
+    assert_eq!(err.to_string(), "Dot 0,0 lies on the fold line y=0");
+    let flipped = paper(&[(0, 1), (0, 3)]).fold((Axis::Y, 0)).unwrap();
+    assert_eq!(flipped.dots, HashSet::from([(0, 2), (0, 0)]));
+    assert_eq!(flipped.height, 3);
+}
@@ -0,0 +1,72 @@
+use advent::days::day15::{Cavern, Day15, Search};
+use advent::grid::{Adjacency, Pos};
+use advent::Solution;
+
+mod common;
+
+fn example() -> Cavern {
+    common::example::<Day15>()
+}
+
+fn risk_along(cave: &Cavern, path: &[Pos]) -> usize {
+    path.iter()
+        .skip(1)
+        .map(|&pos| cave.risk_at(pos).unwrap() as usize)
+        .sum()
+}
+
+#[test]
+fn searches_agree_and_return_connected_path() {
+    let cave = example();
+    let (start, goal) = cave.corners();
+    let dijkstra = cave.find_path(start, goal, Search::Dijkstra).unwrap();
+    let astar = cave.find_path(start, goal, Search::AStar).unwrap();
+    assert_eq!(dijkstra.risk, 40);
+    assert_eq!(astar.risk, 40);
+    for route in [dijkstra, astar] {
+        assert_eq!(route.path.first(), Some(&start));
+        assert_eq!(route.path.last(), Some(&goal));
+        assert!(route
+            .path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+        assert_eq!(risk_along(&cave, &route.path), route.risk);
+    }
+}
+
+#[test]
+fn custom_start_and_goal() {
+    let cave = example();
+    let route = cave.find_path((9, 9), (0, 0), Search::AStar).unwrap();
+    assert_eq!(route.path.first(), Some(&(9, 9)));
+    assert_eq!(risk_along(&cave, &route.path), route.risk);
+
+    let same = cave.find_path((4, 2), (4, 2), Search::Dijkstra).unwrap();
+    assert_eq!(same.risk, 0);
+    assert_eq!(same.path, vec![(4, 2)]);
+
+    assert!(cave.find_path((0, 0), (10, 0), Search::AStar).is_none());
+}
+
+#[test]
+fn paths_may_use_last_row_and_diagonals() {
+    let cave = Day15::parse("199\n111\n").unwrap();
+    let (start, goal) = cave.corners();
+    let route = cave.find_path(start, goal, Search::AStar).unwrap();
+    assert_eq!(route.risk, 3);
+    assert_eq!(route.path, vec![(0, 0), (1, 0), (1, 1), (1, 2)]);
+
+    let cave = cave.with_adjacency(Adjacency::EIGHT);
+    let route = cave.find_path(start, goal, Search::AStar).unwrap();
+    assert_eq!(route.risk, 2);
+    assert_eq!(route.path, vec![(0, 0), (1, 1), (1, 2)]);
+}
+
+#[test]
+fn rejects_zero_risk() {
+    let err = Day15::parse("19\n10\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 2: expected a risk level from 1 to 9, found '0'"
+    );
+}
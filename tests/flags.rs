@@ -0,0 +1,115 @@
+use advent::days::{self, day01::Day01};
+use advent::flags::{parse_range, synopsis, Flag, Flags};
+use advent::Solution;
+
+fn flags(given: &[(&'static str, Option<&str>)]) -> Flags {
+    let mut flags = Flags::default();
+    for &(name, value) in given {
+        let flag = match value {
+            Some(_) => Flag::with_value(name, "VALUE"),
+            None => Flag::switch(name),
+        };
+        flags.push(&flag, value.map(String::from));
+    }
+    flags
+}
+
+#[test]
+fn reads_values_and_switches() {
+    let flags = flags(&[
+        ("--smooth", Some("3")),
+        ("--csv", None),
+        ("--smooth", Some("5")),
+    ]);
+    assert!(flags.has("--csv"));
+    assert!(!flags.has("--profile"));
+    assert_eq!(flags.value("--smooth"), Some("5"));
+    assert_eq!(flags.parse::<usize>("--smooth").unwrap(), Some(5));
+    assert_eq!(flags.parse::<usize>("--after").unwrap(), None);
+    assert_eq!(flags.value("--csv"), None);
+}
+
+#[test]
+fn reports_bad_values() {
+    let flags = flags(&[("--after", Some("x")), ("--sweep", Some("1..y"))]);
+    let err = flags.parse::<usize>("--after").unwrap_err();
+    assert_eq!(err.to_string(), "--after needs a number, found 'x'");
+    let err = flags.range("--sweep").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "--sweep needs a range like 10..20, found '1..y'"
+    );
+}
+
+#[test]
+fn parses_ranges() {
+    assert_eq!(parse_range("--steps", "3").unwrap(), 3..=3);
+    assert_eq!(parse_range("--steps", "3..5").unwrap(), 3..=5);
+    assert_eq!(parse_range("--steps", "3..=5").unwrap(), 3..=5);
+    assert_eq!(parse_range("--steps", "3..").unwrap(), 3..=usize::MAX);
+}
+
+#[test]
+fn days_declare_their_own_flags() {
+    let names = |day| -> Vec<&str> {
+        days::find(day)
+            .unwrap()
+            .flags
+            .iter()
+            .map(|flag| flag.name)
+            .collect()
+    };
+    assert!(names(1).contains(&"--sweep"));
+    assert!(names(2).contains(&"--trace"));
+    assert!(!names(2).contains(&"--sweep"));
+    assert!(names(3).is_empty());
+}
+
+#[test]
+fn rejects_flags_days_do_not_take_or_cannot_use() {
+    let check = |numbers: &[u8], given| {
+        let chosen: Vec<_> = numbers.iter().map(|&n| days::find(n).unwrap()).collect();
+        days::check_flags(&chosen, &flags(given)).map_err(|err| err.to_string())
+    };
+    assert_eq!(check(&[1], &[("--sweep", Some("1..3"))]), Ok(()));
+    assert_eq!(
+        check(&[1, 3], &[("--sweep", Some("1..3"))]),
+        Err("--sweep only applies to day 1".to_string())
+    );
+    assert_eq!(
+        check(&[1], &[("--csv", None)]),
+        Err("--csv needs --sweep or --profile".to_string())
+    );
+    assert_eq!(check(&[1], &[("--csv", None), ("--profile", None)]), Ok(()));
+    assert_eq!(
+        check(&[4], &[("--csv", None)]),
+        Err("--csv only applies to days 1 and 2".to_string())
+    );
+}
+
+#[test]
+fn rejects_smoothing_wider_than_the_depths() {
+    let depths = vec![199, 200, 208, 210];
+    let smooth = |window: &str| {
+        let flags = flags(&[("--profile", None), ("--smooth", Some(window))]);
+        Day01::report(&depths, &flags)
+    };
+    assert!(smooth("4").is_ok());
+    assert_eq!(
+        smooth("100000000000000").unwrap_err().to_string(),
+        "--smooth can't be wider than the 4 depths"
+    );
+    assert_eq!(
+        smooth("18446744073709551615").unwrap_err().to_string(),
+        "--smooth can't be wider than the 4 depths"
+    );
+}
+
+#[test]
+fn writes_synopsis() {
+    let flags = [
+        Flag::with_value("--sweep", "RANGE"),
+        Flag::switch("--csv").needing(&["--sweep"]),
+    ];
+    assert_eq!(synopsis(&flags), ["[--sweep <RANGE>]", "[--csv]"]);
+}
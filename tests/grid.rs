@@ -0,0 +1,112 @@
+use advent::days::day09::Day09;
+use advent::grid::{Adjacency, Grid};
+use advent::{InputError, Solution};
+
+#[test]
+fn four_neighbors_on_non_square_grid() {
+    let size = (2, 3);
+    assert_eq!(
+        Adjacency::FOUR.neighbors((0, 0), size),
+        vec![(1, 0), (0, 1)]
+    );
+    assert_eq!(
+        Adjacency::FOUR.neighbors((1, 2), size),
+        vec![(0, 2), (1, 1)]
+    );
+    assert_eq!(
+        Adjacency::FOUR.neighbors((0, 1), size),
+        vec![(1, 1), (0, 0), (0, 2)]
+    );
+    assert!(Adjacency::FOUR.neighbors((2, 0), size).is_empty());
+}
+
+#[test]
+fn eight_neighbors_on_non_square_grid() {
+    let size = (3, 4);
+    assert_eq!(Adjacency::EIGHT.neighbors((1, 1), size).len(), 8);
+    assert_eq!(
+        Adjacency::EIGHT.neighbors((2, 3), size),
+        vec![(1, 2), (1, 3), (2, 2)]
+    );
+    assert!(Adjacency::EIGHT.neighbors((0, 0), (1, 1)).is_empty());
+}
+
+#[test]
+fn wrapping_neighbors_are_unique() {
+    let size = (2, 5);
+    assert_eq!(
+        Adjacency::FOUR.wrapping().neighbors((0, 0), size),
+        vec![(1, 0), (0, 4), (0, 1)]
+    );
+    assert_eq!(Adjacency::EIGHT.wrapping().neighbors((0, 0), size).len(), 5);
+    assert!(Adjacency::FOUR
+        .wrapping()
+        .neighbors((0, 0), (1, 1))
+        .is_empty());
+}
+
+#[test]
+fn distances() {
+    let size = (3, 10);
+    assert_eq!(Adjacency::FOUR.distance((0, 0), (2, 9), size), 11);
+    assert_eq!(Adjacency::EIGHT.distance((0, 0), (2, 9), size), 9);
+    assert_eq!(Adjacency::FOUR.wrapping().distance((0, 0), (2, 9), size), 2);
+}
+
+#[test]
+fn basins_on_non_square_grid() {
+    let cave = Day09::parse("21999\n39878\n").unwrap();
+    assert_eq!(Day09::part1(&cave).unwrap(), 2 + 8);
+    assert_eq!(Day09::part2(&cave).unwrap(), 3 * 3);
+}
+
+#[test]
+fn parses_digit_grid() {
+    let grid: Grid<u8> = Grid::parse_digits(["123", "456"]).unwrap();
+    assert_eq!(grid.size(), (2, 3));
+    assert_eq!(grid.row(1), &[4, 5, 6]);
+    assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+    assert_eq!(grid[(1, 0)], 4);
+    assert_eq!(grid.get((2, 0)), None);
+    assert_eq!(grid.to_string(), "123\n456");
+}
+
+#[test]
+fn rejects_bad_digit_grids() {
+    let err = Grid::<u8>::parse_digits(["123", "45"]).unwrap_err();
+    assert_eq!(err.to_string(), "Row 2 has 2 cells, expected 3");
+
+    let err = Grid::<u8>::parse_digits(["123", "4x6"]).unwrap_err();
+    let err = err.downcast::<InputError>().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 2: expected a digit, found 'x'"
+    );
+}
+
+#[test]
+fn maps_and_tiles() {
+    let mut grid: Grid<u32> = Grid::parse_digits(["12"]).unwrap();
+    grid.map_in_place(|cell| *cell *= 2);
+    assert_eq!(grid.to_string(), "24");
+
+    let tiled = grid.tile((2, 3), |&cell, (i, j)| cell + (i * 10 + j) as u32);
+    assert_eq!(tiled.size(), (2, 6));
+    assert_eq!(tiled.to_string(), "243546\n121413151416");
+    assert_eq!(
+        tiled
+            .map(|&cell| cell > 10)
+            .values()
+            .filter(|&&b| b)
+            .count(),
+        6
+    );
+}
+
+#[test]
+fn neighbor_iterators() {
+    let grid = Grid::filled(3, 2, 0u8);
+    assert_eq!(grid.neighbors((0, 0), Adjacency::FOUR).count(), 2);
+    assert_eq!(grid.neighbors((1, 0), Adjacency::EIGHT).count(), 5);
+    assert_eq!(grid.positions().count(), 6);
+}
@@ -0,0 +1,108 @@
+use advent::days::{day09::Day09, day13::Day13, day15::Day15};
+use advent::grid::Grid;
+use advent::image::{self, Format, Overlay, Picture};
+use advent::Solution;
+use std::path::Path;
+
+mod common;
+
+fn example_picture<S: Solution>() -> Picture {
+    S::picture(&common::example::<S>())
+        .unwrap()
+        .expect("day can be exported")
+}
+
+fn checkerboard() -> Picture {
+    let base = Grid::from_rows(vec![
+        vec![image::BLACK, image::WHITE],
+        vec![image::WHITE, image::BLACK],
+    ])
+    .unwrap();
+    Picture::new(base).with_scale(1)
+}
+
+#[test]
+fn format_from_extension() {
+    assert_eq!(
+        Format::from_path(Path::new("out.PNG")).unwrap(),
+        Format::Png
+    );
+    assert_eq!(
+        Format::from_path(Path::new("a/b.svg")).unwrap(),
+        Format::Svg
+    );
+    let err = Format::from_path(Path::new("out.jpg")).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("expected a .ppm, .png or .svg file"));
+}
+
+#[test]
+fn ppm_scales_cells() {
+    let ppm = checkerboard().with_scale(2).to_ppm();
+    let (header, pixels) = ppm.split_at(b"P6\n4 4\n255\n".len());
+    assert_eq!(header, b"P6\n4 4\n255\n");
+    assert_eq!(pixels.len(), 4 * 4 * 3);
+    assert_eq!(
+        &pixels[..12],
+        &[0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255]
+    );
+}
+
+#[test]
+fn png_is_stored_zlib() {
+    let png = Picture::new(Grid::filled(1, 1, image::BLACK))
+        .with_scale(1)
+        .to_png();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    // One scanline: filter byte and a black pixel, in a single stored block
+    let idat = [
+        0x78, 0x01, 0x01, 0x04, 0x00, 0xfb, 0xff, 0, 0, 0, 0, 0x00, 0x04, 0x00, 0x01,
+    ];
+    let start = png.windows(4).position(|w| w == b"IDAT").unwrap() + 4;
+    assert_eq!(&png[start..start + idat.len()], &idat);
+    assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+}
+
+#[test]
+fn overlays_paint_over_base() {
+    let picture = checkerboard()
+        .with_overlay(Overlay::Region {
+            cells: vec![(0, 0), (5, 5)],
+            color: image::RED,
+        })
+        .with_overlay(Overlay::Path {
+            cells: vec![(0, 1), (1, 1)],
+            color: image::BLACK,
+        });
+    let cells = picture.cells();
+    assert_eq!(cells[(0, 0)], image::RED);
+    assert_eq!(cells[(0, 1)], image::BLACK);
+    assert_eq!(cells[(1, 0)], image::WHITE);
+
+    let svg = picture.to_svg();
+    assert!(svg.contains("viewBox=\"0 0 2 2\""));
+    assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"#dc1e1e\"/>"));
+    // SVG draws paths as lines instead of filling their cells
+    assert!(svg.contains("<rect x=\"1\" y=\"0\" width=\"1\" height=\"1\" fill=\"#ffffff\"/>"));
+    assert!(svg.contains("<polyline points=\"1.5,0.5 1.5,1.5\""));
+}
+
+#[test]
+fn day_pictures() {
+    let basins = example_picture::<Day09>().cells();
+    assert_eq!(basins.size(), (5, 10));
+    assert_eq!(basins[(0, 2)], image::gray(9, 9));
+    assert_eq!(basins[(0, 0)], basins[(1, 0)]);
+    assert_ne!(basins[(0, 0)], basins[(0, 9)]);
+
+    let dots = example_picture::<Day13>().cells();
+    assert_eq!(dots.size(), (7, 5));
+    assert_eq!(dots[(0, 0)], image::BLACK);
+    assert_eq!(dots[(2, 2)], image::WHITE);
+
+    let route = example_picture::<Day15>().cells();
+    assert_eq!(route[(0, 0)], image::RED);
+    assert_eq!(route[(9, 9)], image::RED);
+    assert_eq!(route.values().filter(|&&rgb| rgb == image::RED).count(), 19);
+}
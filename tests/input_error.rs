@@ -0,0 +1,37 @@
+use advent::days::day02::Day02;
+use advent::{InputError, Location};
+
+mod common;
+
+use common::parse_error;
+
+#[test]
+fn bad_direction_points_at_token() {
+    let err = parse_error::<Day02>("forward 5\n  sideways 3\n");
+    assert_eq!(
+        err.location(),
+        &Location {
+            line: Some(2),
+            span: 2..10
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "line 2, columns 3-10: expected forward, back, up, down, turn or repeat, found 'sideways'"
+    );
+}
+
+#[test]
+fn bad_distance_keeps_source() {
+    let err = parse_error::<Day02>("down 5\nup 3\nup x7");
+    assert!(matches!(&err, InputError::Invalid { text, .. } if text == "x7"));
+    assert_eq!(err.location().span, 3..5);
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn missing_distance_points_past_direction() {
+    let err = parse_error::<Day02>("down");
+    assert!(matches!(&err, InputError::Missing { .. }));
+    assert_eq!(err.to_string(), "line 1, column 5: missing a distance");
+}
@@ -0,0 +1,74 @@
+use advent::grid::Grid;
+use advent::ocr::{self, UnknownGlyph};
+
+fn image(art: &str) -> Grid<bool> {
+    Grid::from_rows(
+        art.lines()
+            .map(|line| line.chars().map(|c| c == '#').collect()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn reads_letters() {
+    let reading = ocr::read(&image(
+        ".##..###..#..#.####.#....\n\
+         #..#.#..#.#..#.#....#....\n\
+         #..#.###..####.###..#....\n\
+         ####.#..#.#..#.#....#....\n\
+         #..#.#..#.#..#.#....#....\n\
+         #..#.###..#..#.####.####.",
+    ));
+    assert_eq!(reading.text, "ABHEL");
+    assert!(reading.unknown.is_empty());
+    assert_eq!(reading.to_string(), "ABHEL");
+}
+
+#[test]
+fn reports_unknown_glyphs() {
+    // An O with a stray dot in its gap, then a Z missing its last stroke
+    let reading = ocr::read(&image(
+        ".##.#.##..####\n\
+         #..#.#..#....#\n\
+         #..#.#..#...#.\n\
+         #..#.#..#..#..\n\
+         #..#.#..#.#...\n\
+         .##...##..###.",
+    ));
+    assert_eq!(reading.text, "?O?");
+    assert_eq!(
+        reading.unknown[0],
+        UnknownGlyph {
+            index: 0,
+            columns: 0..5,
+            art: ".##.#\n#..#.\n#..#.\n#..#.\n#..#.\n.##..".to_string(),
+        }
+    );
+    assert_eq!(
+        (reading.unknown[1].index, reading.unknown[1].columns.clone()),
+        (2, 10..14)
+    );
+    assert_eq!(
+        reading.to_string(),
+        "?O? (unknown glyphs at columns 0-4, 10-13)\n \
+         ## #\n#  #\n#  #\n#  #\n#  #\n ##\n\n\
+         ####\n   #\n  #\n #\n#\n###"
+    );
+}
+
+#[test]
+fn short_and_tall_images() {
+    let square = ocr::read(&image("#####\n#...#\n#...#\n#...#\n#####"));
+    assert_eq!(
+        square.to_string(),
+        "? (unknown glyph at columns 0-4)\n#####\n#   #\n#   #\n#   #\n#####"
+    );
+    assert_eq!(square.unknown[0].art.lines().count(), 6);
+
+    let tall = ocr::read(&image("#...\n#...\n#...\n#...\n#...\n####\n...#"));
+    assert_eq!(tall.text, "?");
+    assert_eq!(
+        ocr::read(&image("#...\n#...\n#...\n#...\n#...\n####")).text,
+        "L"
+    );
+}
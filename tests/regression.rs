@@ -0,0 +1,131 @@
+use advent::days::day13::Day13;
+use advent::days::{self, DAYS};
+use advent::{ocr, read_lines, InputLocator};
+use anyhow::{Context, Result};
+
+mod common;
+
+/// Known answers for a day: on the worked example and on the committed input.
+struct Expected {
+    day: u8,
+    example: [&'static str; 2],
+    input: [&'static str; 2],
+}
+
+const DAY13_EXAMPLE: &str = "
+#####
+#   #
+#   #
+#   #
+#####";
+
+const DAY13_EXAMPLE_READING: &str = "? (unknown glyph at columns 0-4)
+#####
+#   #
+#   #
+#   #
+#####";
+
+#[rustfmt::skip]
+const EXPECTED: &[Expected] = &[
+    Expected { day: 1, example: ["7", "5"], input: ["1722", "1748"] },
+    Expected { day: 2, example: ["150", "900"], input: ["2120749", "2138382217"] },
+    Expected { day: 3, example: ["198", "230"], input: ["1307354", "482500"] },
+    Expected { day: 4, example: ["4512", "1924"], input: ["12796", "18063"] },
+    Expected { day: 5, example: ["5", "12"], input: ["4826", "16793"] },
+    Expected { day: 6, example: ["5934", "26984457539"], input: ["362740", "1644874076764"] },
+    Expected { day: 7, example: ["37", "168"], input: ["355592", "101618069"] },
+    Expected { day: 8, example: ["0", "5353"], input: ["318", "996280"] },
+    Expected { day: 9, example: ["15", "1134"], input: ["541", "847504"] },
+    Expected { day: 10, example: ["26397", "288957"], input: ["271245", "1685293086"] },
+    Expected { day: 11, example: ["1656", "195"], input: ["1691", "216"] },
+    Expected { day: 12, example: ["10", "36"], input: ["3738", "120506"] },
+    Expected { day: 13, example: ["17", DAY13_EXAMPLE_READING], input: ["684", "JRZBLGKH"] },
+    Expected { day: 14, example: ["1588", "2188189693529"], input: ["3906", "4441317262452"] },
+    Expected { day: 15, example: ["40", "315"], input: ["698", "3022"] },
+];
+
+/// Ignores trailing whitespace, which rendered answers may or may not carry.
+fn normalize(answer: &str) -> String {
+    let lines: Vec<&str> = answer.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+fn indent(answer: &str) -> String {
+    answer.replace('\n', "\n              ")
+}
+
+fn solve(day: u8, input: Result<String>) -> Result<[String; 2]> {
+    let day = days::find(day).with_context(|| format!("Day {} isn't registered", day))?;
+    let (part1, part2) = (day.solve)(&input?)?;
+    Ok([part1, part2])
+}
+
+/// Solves every day on the input `read` returns, reporting all mismatches at once.
+fn check(kind: &str, read: impl Fn(u8) -> Result<String>, pick: fn(&Expected) -> [&str; 2]) {
+    let mut failures = Vec::new();
+    for expected in EXPECTED {
+        let actual = match solve(expected.day, read(expected.day)) {
+            Ok(answers) => answers,
+            Err(err) => {
+                let err = format!("error: {:#}", err);
+                [err.clone(), err]
+            }
+        };
+        for (part, (want, got)) in pick(expected).iter().zip(actual).enumerate() {
+            if normalize(want) != normalize(&got) {
+                failures.push(format!(
+                    "day {:02} part {} ({}):\n    expected: {}\n      actual: {}",
+                    expected.day,
+                    part + 1,
+                    kind,
+                    indent(&normalize(want)),
+                    indent(&normalize(&got)),
+                ));
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} answer(s) changed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn every_day_has_expected_answers() {
+    let registered: Vec<u8> = DAYS.iter().map(|day| day.number).collect();
+    let expected: Vec<u8> = EXPECTED.iter().map(|e| e.day).collect();
+    assert_eq!(registered, expected);
+}
+
+#[test]
+fn examples() {
+    check(
+        "example",
+        |day| read_lines(common::example_path(day)),
+        |e| e.example,
+    );
+}
+
+#[test]
+fn inputs() {
+    let locator = InputLocator::default();
+    check("input", |day| locator.read(day), |e| e.input);
+}
+
+/// The example folds into a square rather than a letter, so check the shape
+/// itself as well as what the OCR makes of it.
+#[test]
+fn day13_example_folds_into_square() {
+    let (paper, folds) = common::example::<Day13>();
+    let grid = paper.fold_all(&folds).unwrap().to_grid();
+    let drawn = grid.map(|&dot| if dot { '#' } else { ' ' }).to_string();
+    assert_eq!(normalize(&drawn), normalize(DAY13_EXAMPLE));
+
+    let reading = ocr::read(&grid);
+    assert_eq!(reading.text, "?");
+    assert_eq!(reading.unknown.len(), 1);
+    assert_eq!(reading.unknown[0].columns, 0..5);
+}
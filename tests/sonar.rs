@@ -0,0 +1,154 @@
+use advent::sonar::{
+    extrema, largest_drop, longest_increasing_run, smooth, sweep_sizes, sweep_table,
+    windowed_sweep, Change, DepthDrop, Extremum, Profile, Tally, Window,
+};
+use advent::table::Table;
+
+const EXAMPLE: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+#[test]
+fn window_keeps_running_sum() {
+    let mut window = Window::new(3);
+    assert_eq!(window.push(199), None);
+    assert_eq!(window.push(200), None);
+    assert_eq!(window.sum(), None);
+    assert_eq!(window.push(208), None);
+    assert_eq!(window.sum(), Some(607));
+    assert_eq!(window.push(210), Some(Change::Increase));
+    assert_eq!(window.push(200), Some(Change::Plateau));
+    assert_eq!(window.push(199), Some(Change::Decrease));
+    assert_eq!(window.sum(), Some(609));
+}
+
+#[test]
+fn tallies_every_kind_of_change() {
+    let tally = windowed_sweep(&EXAMPLE, 3);
+    assert_eq!(
+        tally,
+        Tally {
+            increases: 5,
+            decreases: 1,
+            plateaus: 1
+        }
+    );
+    assert_eq!(tally.count(Change::Plateau), 1);
+    assert_eq!(windowed_sweep(&[5, 5, 5], 1).plateaus, 2);
+}
+
+#[test]
+#[should_panic(expected = "Window size must be at least 1")]
+fn empty_window() {
+    Window::new(0);
+}
+
+#[test]
+fn sweeps_window_sizes() {
+    let sweeps = sweep_sizes(&EXAMPLE, 0..=usize::MAX);
+    let sizes: Vec<usize> = sweeps.iter().map(|(size, _)| *size).collect();
+    assert_eq!(sizes, (1..=10).collect::<Vec<_>>());
+    assert_eq!(sweeps[0].1.increases, 7);
+    assert_eq!(sweeps[9].1, Tally::default());
+
+    let table = sweep_table(&sweep_sizes(&EXAMPLE, 1..=2));
+    let expected = [
+        "Window  Increases  Decreases  Plateaus",
+        "     1          7          2         0",
+        "     2          5          3         0",
+    ];
+    assert_eq!(table.to_string(), expected.join("\n"));
+}
+
+#[test]
+fn finds_longest_increasing_run() {
+    assert_eq!(longest_increasing_run(&EXAMPLE), Some(0..4));
+    assert_eq!(longest_increasing_run(&[5, 5, 4, 6, 7]), Some(2..5));
+    assert_eq!(longest_increasing_run(&[3]), Some(0..1));
+    assert_eq!(longest_increasing_run(&[]), None);
+}
+
+#[test]
+fn finds_largest_drop() {
+    let drop = DepthDrop {
+        index: 4,
+        from: 210,
+        to: 200,
+    };
+    assert_eq!(largest_drop(&EXAMPLE), Some(drop));
+    assert_eq!(drop.size(), 10);
+    assert_eq!(largest_drop(&[1, 2, 3]), None);
+}
+
+#[test]
+fn smooths_over_trailing_window() {
+    let smoothed = smooth(&[3, 6, 9, 3], 2);
+    assert_eq!(smoothed, vec![None, Some(4.5), Some(7.5), Some(6.0)]);
+    assert_eq!(smooth(&[1, 2], 3), vec![None, None]);
+}
+
+#[test]
+fn collapses_plateaus_into_one_extremum() {
+    let series = [
+        None,
+        Some(1.0),
+        Some(3.0),
+        Some(3.0),
+        Some(2.0),
+        Some(2.0),
+        Some(4.0),
+    ];
+    assert_eq!(
+        extrema(&series),
+        vec![(2, Extremum::Maximum), (4, Extremum::Minimum)]
+    );
+    // A step up isn't a turning point
+    assert_eq!(
+        extrema(&[Some(1.0), Some(2.0), Some(2.0), Some(3.0)]),
+        vec![]
+    );
+}
+
+#[test]
+fn profiles_example() {
+    let profile = Profile::new(&EXAMPLE, 3);
+    assert_eq!(
+        profile.extrema,
+        vec![(3, Extremum::Maximum), (5, Extremum::Minimum)]
+    );
+    let summary = profile.summary();
+    assert_eq!(summary.rows()[0], ["Longest increasing run", "4", "0..=3"]);
+    assert_eq!(summary.rows()[1], ["Largest drop", "10", "210 -> 200 at 4"]);
+    let series = profile.series();
+    assert_eq!(series.rows().len(), EXAMPLE.len());
+    assert_eq!(series.rows()[1], ["1", "200", "", ""]);
+    assert_eq!(series.rows()[3], ["3", "210", "206.00", "max"]);
+}
+
+#[test]
+fn writes_csv() {
+    let mut table = Table::new(["Name", "Note"]);
+    table.push(["plain", "a, b"]);
+    table.push(["quoted \"x\"", ""]);
+    assert_eq!(
+        table.to_csv(),
+        "Name,Note\nplain,\"a, b\"\n\"quoted \"\"x\"\"\",\n"
+    );
+}
+
+#[test]
+fn streams_rows_in_pages() {
+    let rows = || (1..=3).map(|n| vec![n.to_string(), "x".repeat(n)]);
+    let stream = |csv, page| {
+        let mut out = Vec::new();
+        Table::stream(&mut out, ["N", "Cells"], rows(), csv, page).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    assert_eq!(stream(true, 1), "N,Cells\n1,x\n2,xx\n3,xxx\n");
+    assert_eq!(
+        stream(false, 2),
+        "N  Cells\n1      x\n2     xx\n\nN  Cells\n3    xxx\n"
+    );
+    assert_eq!(stream(false, 3), "N  Cells\n1      x\n2     xx\n3    xxx\n");
+    let mut out = Vec::new();
+    Table::stream(&mut out, ["N"], std::iter::empty(), false, 2).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "N\n");
+}